@@ -0,0 +1,70 @@
+use std::fmt::{Display, Formatter};
+
+/// The answer to one part of a puzzle.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Answer {
+    Integer(i64),
+    /// An integer too large for an `i64`.
+    BigInteger(i128),
+    Text(String),
+    /// Text spanning several lines, such as letters drawn on a grid.
+    Lines(Vec<String>),
+}
+
+impl Answer {
+    pub fn is_multiline(&self) -> bool {
+        matches!(self, Answer::Lines(lines) if lines.len() > 1)
+    }
+
+    /// The answer, if there is one, or else `text` explaining why there isn't.
+    pub fn or_text(answer: Option<impl Into<Answer>>, text: &str) -> Answer {
+        answer.map_or_else(|| Answer::Text(text.to_string()), Into::into)
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Integer(n) => write!(f, "{}", n),
+            Answer::BigInteger(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+            Answer::Lines(lines) => write!(f, "{}", lines.join("\n")),
+        }
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(n: i64) -> Self {
+        Answer::Integer(n)
+    }
+}
+
+impl From<i128> for Answer {
+    fn from(n: i128) -> Self {
+        i64::try_from(n).map_or(Answer::BigInteger(n), Answer::Integer)
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(n: u64) -> Self {
+        Answer::from(n as i128)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(n: usize) -> Self {
+        Answer::from(n as i128)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+impl From<Vec<String>> for Answer {
+    fn from(lines: Vec<String>) -> Self {
+        Answer::Lines(lines)
+    }
+}
@@ -0,0 +1,228 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+
+pub const DEFAULT_ANSWERS_PATH: &str = "answers.toml";
+
+/// Known-correct answers, keyed by day and part.
+///
+/// The file is a small subset of TOML: one `[dayN]` table per day holding `part1` and `part2`
+/// keys, whose values are integers, `"strings"` or `"""multi-line strings"""`. Comments start
+/// with `#`, either on a line of their own or after a value.
+///
+/// ```toml
+/// [day1]
+/// part1 = 1393
+/// part2 = 1359 # after fixing the off-by-one
+/// ```
+pub struct Answers {
+    answers: BTreeMap<(u8, u8), String>,
+}
+
+#[derive(Debug)]
+pub struct AnswersError {
+    path: PathBuf,
+    line: usize,
+    reason: String,
+}
+
+impl Display for AnswersError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.line == 0 {
+            write!(f, "{}: {}", self.path.display(), self.reason)
+        } else {
+            write!(f, "{}:{}: {}", self.path.display(), self.line, self.reason)
+        }
+    }
+}
+
+impl Error for AnswersError {}
+
+impl Answers {
+    pub fn load(path: &Path) -> Result<Self, AnswersError> {
+        let contents = std::fs::read_to_string(path).map_err(|e| AnswersError {
+            path: path.to_path_buf(),
+            line: 0,
+            reason: e.to_string(),
+        })?;
+        Self::parse(&contents).map_err(|(line, reason)| AnswersError {
+            path: path.to_path_buf(),
+            line,
+            reason,
+        })
+    }
+
+    /// Parses the contents of an answers file, reporting failures as `(line number, reason)`.
+    fn parse(contents: &str) -> Result<Self, (usize, String)> {
+        let mut answers = BTreeMap::new();
+        let mut day = None;
+        let mut lines = contents.lines().enumerate().map(|(i, l)| (i + 1, l));
+        while let Some((n, line)) = lines.next() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some(table) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                let parsed = table
+                    .trim()
+                    .strip_prefix("day")
+                    .and_then(|d| d.parse().ok());
+                day = Some(parsed.ok_or((n, format!("expected a [dayN] table, got [{}]", table)))?);
+                continue;
+            }
+            let day = day.ok_or((n, String::from("answer given outside of a [dayN] table")))?;
+            let (key, value) = line
+                .split_once('=')
+                .ok_or((n, String::from("expected `key = value`")))?;
+            let part = match key.trim() {
+                "part1" => 1,
+                "part2" => 2,
+                other => return Err((n, format!("unknown key `{}`", other))),
+            };
+            let value = value.trim();
+            let answer = if let Some(rest) = value.strip_prefix(r#"""""#) {
+                // Like TOML, a newline directly after the opening quotes is not part of the value.
+                // Unlike TOML, neither is a newline directly before the closing quotes, so they
+                // can go on a line of their own.
+                let mut text = String::from(rest);
+                let mut end_line = n;
+                while !text.contains(r#"""""#) {
+                    let (m, next) = lines
+                        .next()
+                        .ok_or((n, String::from("unterminated multi-line string")))?;
+                    text.push('\n');
+                    text.push_str(next);
+                    end_line = m;
+                }
+                let (text, after) = text
+                    .split_once(r#"""""#)
+                    .expect("Closing quotes were found");
+                expect_comment(after).ok_or((end_line, format!("unexpected {}", after.trim())))?;
+                let text = text.strip_prefix('\n').unwrap_or(text);
+                String::from(text.strip_suffix('\n').unwrap_or(text))
+            } else if let Some(rest) = value.strip_prefix('"') {
+                let (quoted, after) =
+                    split_string(rest).ok_or((n, format!("unterminated string {}", value)))?;
+                expect_comment(after).ok_or((n, format!("unexpected {}", after.trim())))?;
+                unescape(quoted).ok_or((n, format!("invalid escape in {}", value)))?
+            } else {
+                let value = value.split('#').next().unwrap_or_default().trim();
+                value
+                    .replace('_', "")
+                    .parse::<i128>()
+                    .map_err(|_| (n, format!("expected an integer or string, got {}", value)))?
+                    .to_string()
+            };
+            if answers.insert((day, part), answer).is_some() {
+                return Err((n, format!("duplicate answer for day {} part {}", day, part)));
+            }
+        }
+        Ok(Answers { answers })
+    }
+
+    pub fn get(&self, day: u8, part: u8) -> Option<&str> {
+        self.answers.get(&(day, part)).map(String::as_str)
+    }
+}
+
+/// Splits the body of a `"string"`, after its opening quote, at the closing quote, returning the
+/// still-escaped contents and whatever follows the closing quote.
+fn split_string(s: &str) -> Option<(&str, &str)> {
+    let mut escaped = false;
+    for (i, ch) in s.char_indices() {
+        match ch {
+            '"' if !escaped => return Some((&s[..i], &s[i + 1..])),
+            '\\' => escaped = !escaped,
+            _ => escaped = false,
+        }
+    }
+    None
+}
+
+/// Checks that what follows a value is nothing but whitespace and an optional `# comment`.
+fn expect_comment(after: &str) -> Option<()> {
+    let after = after.trim();
+    (after.is_empty() || after.starts_with('#')).then_some(())
+}
+
+fn unescape(s: &str) -> Option<String> {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(ch) = chars.next() {
+        if ch != '\\' {
+            out.push(ch);
+            continue;
+        }
+        out.push(match chars.next()? {
+            'n' => '\n',
+            't' => '\t',
+            '"' => '"',
+            '\\' => '\\',
+            _ => return None,
+        });
+    }
+    Some(out)
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Verdict {
+    Pass,
+    Fail,
+    Missing,
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Verdict::Pass => "PASS",
+            Verdict::Fail => "FAIL",
+            Verdict::Missing => "MISSING",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// Compares a computed answer to the stored one, if there is one.
+pub fn check(expected: Option<&str>, actual: &str) -> Verdict {
+    match expected {
+        None => Verdict::Missing,
+        Some(expected) if expected == actual => Verdict::Pass,
+        Some(_) => Verdict::Fail,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_multi_line_strings_closed_on_their_own_line() {
+        let answers = Answers::parse("[day13]\npart2 = \"\"\"\n#..#\n####\n\"\"\"\n").unwrap();
+        assert_eq!(answers.get(13, 2), Some("#..#\n####"));
+        assert_eq!(check(answers.get(13, 2), "#..#\n####"), Verdict::Pass);
+    }
+
+    #[test]
+    fn parses_multi_line_strings_closed_on_the_last_line() {
+        let answers = Answers::parse("[day13]\npart2 = \"\"\"#..#\n####\"\"\"\n").unwrap();
+        assert_eq!(answers.get(13, 2), Some("#..#\n####"));
+    }
+
+    #[test]
+    fn ignores_trailing_comments() {
+        let answers = Answers::parse(
+            "[day1] \npart1 = 17 # note\npart2 = \"a # b\" # note\n[day2]\npart1 = \"\"\"\nx\n\"\"\" # note\n",
+        )
+        .unwrap();
+        assert_eq!(answers.get(1, 1), Some("17"));
+        assert_eq!(answers.get(1, 2), Some("a # b"));
+        assert_eq!(answers.get(2, 1), Some("x"));
+    }
+
+    #[test]
+    fn rejects_text_after_values() {
+        assert!(Answers::parse("[day1]\npart1 = \"17\" 18\n").is_err());
+        assert!(Answers::parse("[day1]\npart1 = 17 18\n").is_err());
+    }
+}
@@ -0,0 +1,129 @@
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+use std::time::Duration;
+
+/// The stages of a day that are benchmarked independently of each other.
+pub const STAGES: [&str; 3] = ["parse", "part_one", "part_two"];
+
+/// Raw timings for every iteration of each stage, indexed in the same order as [`STAGES`].
+pub struct Samples {
+    pub stages: [Vec<Duration>; 3],
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        assert!(!samples.is_empty(), "Need at least one sample");
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+        Stats {
+            min: sorted[0],
+            median: sorted[sorted.len() / 2],
+            max: sorted[sorted.len() - 1],
+        }
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "min {:>10.2?} | median {:>10.2?} | max {:>10.2?}",
+            self.min, self.median, self.max
+        )
+    }
+}
+
+/// Stored statistics keyed by day and stage name.
+///
+/// On disk, each line is `<day> <stage> <min ns> <median ns> <max ns>`.
+#[derive(Default)]
+pub struct Baseline {
+    entries: BTreeMap<(u8, String), Stats>,
+}
+
+impl Baseline {
+    pub fn load(path: &Path) -> std::io::Result<Self> {
+        let contents = std::fs::read_to_string(path)?;
+        let mut entries = BTreeMap::new();
+        for (i, line) in contents.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let (key, stats) = Self::parse_entry(line).ok_or_else(|| {
+                std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("{}:{}: malformed baseline entry", path.display(), i + 1),
+                )
+            })?;
+            entries.insert(key, stats);
+        }
+        Ok(Baseline { entries })
+    }
+
+    fn parse_entry(line: &str) -> Option<((u8, String), Stats)> {
+        let nanos = |s: &str| s.parse().map(Duration::from_nanos).ok();
+        match line.split_ascii_whitespace().collect::<Vec<_>>().as_slice() {
+            &[day, stage, min, median, max] => {
+                let stats = Stats {
+                    min: nanos(min)?,
+                    median: nanos(median)?,
+                    max: nanos(max)?,
+                };
+                Some(((day.parse().ok()?, stage.to_string()), stats))
+            }
+            _ => None,
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        let contents: String = self
+            .entries
+            .iter()
+            .map(|((day, stage), stats)| {
+                format!(
+                    "{} {} {} {} {}\n",
+                    day,
+                    stage,
+                    stats.min.as_nanos(),
+                    stats.median.as_nanos(),
+                    stats.max.as_nanos()
+                )
+            })
+            .collect();
+        std::fs::write(path, contents)
+    }
+
+    pub fn get(&self, day: u8, stage: &str) -> Option<Stats> {
+        self.entries.get(&(day, stage.to_string())).copied()
+    }
+
+    pub fn insert(&mut self, day: u8, stage: &str, stats: Stats) {
+        self.entries.insert((day, stage.to_string()), stats);
+    }
+}
+
+/// Describes how `current` compares to `baseline` by their medians, e.g. `12.5% faster`.
+pub fn compare(current: Stats, baseline: Stats) -> String {
+    let before = baseline.median.as_secs_f64();
+    let after = current.median.as_secs_f64();
+    if before == 0.0 {
+        return String::from("no baseline timing");
+    }
+    let change = (after - before) / before * 100.0;
+    // Anything that would round to `0.0%` either way is reported as no change.
+    if change.abs() < 0.05 {
+        String::from("no change")
+    } else if change < 0.0 {
+        format!("{:.1}% faster", change.abs())
+    } else {
+        format!("{:.1}% slower", change)
+    }
+}
@@ -1,11 +1,14 @@
-use crate::utils::{input, parse_input};
+use crate::answer::Answer;
+use crate::error::Result;
+use crate::solver::Solver;
+use crate::utils::parse_input;
 
-fn num_increases(xs: &[i64]) -> usize {
+pub fn num_increases(xs: &[i64]) -> usize {
     // Special case w/ windows where we want to see adjacent pairs -> size-1 adjacent windows
     num_k_window_increases(xs, 1)
 }
 
-fn num_k_window_increases(xs: &[i64], k: usize) -> usize {
+pub fn num_k_window_increases(xs: &[i64], k: usize) -> usize {
     assert_ne!(k, 0, "nonsensical for size-0 window case");
     // This works because size-k adjacent windows will only differ in one:
     // over [a, b, c, d] with size-3 windows, we must only compare a and d, as b and c are in both.
@@ -14,10 +17,34 @@ fn num_k_window_increases(xs: &[i64], k: usize) -> usize {
         .sum()
 }
 
-pub(crate) fn solve() -> (i64, i64) {
-    let v = parse_input(&input());
-    (
-        num_increases(&v) as i64,
-        num_k_window_increases(&v, 3) as i64,
-    )
+pub fn parse(input: &str) -> Result<Vec<i64>> {
+    parse_input(input)
+}
+
+pub fn part_one(depths: &[i64]) -> usize {
+    num_increases(depths)
+}
+
+pub fn part_two(depths: &[i64]) -> usize {
+    num_k_window_increases(depths, 3)
+}
+
+pub struct Day1;
+
+impl Solver for Day1 {
+    const DAY: u8 = 1;
+    const NAME: &'static str = "Sonar Sweep";
+    type Input = Vec<i64>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part_one(depths: &Self::Input) -> Answer {
+        part_one(depths).into()
+    }
+
+    fn part_two(depths: &Self::Input) -> Answer {
+        part_two(depths).into()
+    }
 }
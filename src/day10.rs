@@ -1,10 +1,12 @@
+use crate::answer::Answer;
 use crate::day10::DelimiterType::{Angle, Curly, Paren, Square};
-use crate::utils::input;
+use crate::error::{self, Error};
+use crate::solver::Solver;
 use std::convert::TryFrom;
 use std::fmt::{Display, Formatter};
 
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
-enum DelimiterType {
+pub enum DelimiterType {
     Paren,
     Square,
     Curly,
@@ -12,7 +14,7 @@ enum DelimiterType {
 }
 
 impl DelimiterType {
-    fn syntax_score(&self) -> i64 {
+    pub fn syntax_score(&self) -> i64 {
         match self {
             Paren => 3,
             Square => 57,
@@ -21,7 +23,7 @@ impl DelimiterType {
         }
     }
 
-    fn autocomplete_score(&self) -> i64 {
+    pub fn autocomplete_score(&self) -> i64 {
         match self {
             Paren => 1,
             Square => 2,
@@ -32,9 +34,9 @@ impl DelimiterType {
 }
 
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
-struct Delimiter {
-    ty: DelimiterType,
-    opening: bool,
+pub struct Delimiter {
+    pub ty: DelimiterType,
+    pub opening: bool,
 }
 
 impl Display for Delimiter {
@@ -54,7 +56,13 @@ impl Display for Delimiter {
 }
 
 #[derive(Debug)]
-struct ParseDelimiterError;
+pub struct ParseDelimiterError;
+
+impl Display for ParseDelimiterError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Failed to parse delimiter")
+    }
+}
 
 impl TryFrom<char> for Delimiter {
     type Error = ParseDelimiterError;
@@ -98,70 +106,69 @@ impl TryFrom<char> for Delimiter {
     }
 }
 
-fn to_delimiters(line: &str) -> Vec<Delimiter> {
-    line.chars()
-        .map(Delimiter::try_from)
-        .collect::<Result<Vec<_>, _>>()
-        .unwrap()
+fn to_delimiters(line_index: usize, line: &str) -> error::Result<Vec<Delimiter>> {
+    line.char_indices()
+        .map(|(i, ch)| {
+            Delimiter::try_from(ch)
+                .map_err(|e| Error::parse(line_index, line, &line[i..i + ch.len_utf8()], e))
+        })
+        .collect()
 }
 
-fn part_one(lines: &[&[Delimiter]]) -> i64 {
+/// The outcome of matching up every delimiter in a line.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Check {
+    /// The first closing delimiter that doesn't match the last unclosed opening delimiter.
+    Corrupted(Delimiter),
+    /// The opening delimiters still unclosed at the end of the line, innermost last.
+    /// A line with every delimiter closed is `Incomplete` with nothing left open.
+    Incomplete(Vec<Delimiter>),
+}
+
+pub fn check(line: &[Delimiter]) -> Check {
     let mut stack: Vec<Delimiter> = Vec::new();
-    let mut score = 0;
-    for &line in lines {
-        stack.clear();
-        for &delim in line {
-            if let Some(&last_added) = stack.last() {
-                if last_added.opening && !delim.opening {
-                    if last_added.ty != delim.ty {
-                        // Mismatch / corruption: last opening
-                        score += delim.ty.syntax_score();
-                        break;
-                    } else {
-                        stack.pop();
-                    }
+    for &delim in line {
+        if let Some(&last_added) = stack.last() {
+            if last_added.opening && !delim.opening {
+                if last_added.ty != delim.ty {
+                    // Mismatch / corruption: last opening doesn't match
+                    return Check::Corrupted(delim);
+                } else {
+                    stack.pop();
                 }
             }
-            if delim.opening {
-                stack.push(delim);
-            }
+        }
+        if delim.opening {
+            stack.push(delim);
         }
     }
-    score
+    Check::Incomplete(stack)
 }
 
-fn part_two(lines: &[&[Delimiter]]) -> i64 {
-    let mut stack: Vec<Delimiter> = Vec::new();
-    let mut scores: Vec<i64> = Vec::new();
-    for &line in lines {
-        stack.clear();
-        let mut broke = false;
-        for &delim in line {
-            if let Some(&last_added) = stack.last() {
-                if last_added.opening && !delim.opening {
-                    if last_added.ty != delim.ty {
-                        // Mismatch / corruption: last opening doesn't match
-                        broke = true;
-                        break;
-                    } else {
-                        stack.pop();
-                    }
-                }
-            }
-            if delim.opening {
-                stack.push(delim);
-            }
-        }
-        if !broke {
-            scores.push(
+pub fn part_one(lines: &[&[Delimiter]]) -> i64 {
+    lines
+        .iter()
+        .map(|line| match check(line) {
+            Check::Corrupted(delim) => delim.ty.syntax_score(),
+            Check::Incomplete(_) => 0,
+        })
+        .sum()
+}
+
+pub fn part_two(lines: &[&[Delimiter]]) -> i64 {
+    let mut scores: Vec<i64> = lines
+        .iter()
+        .filter_map(|line| match check(line) {
+            Check::Corrupted(_) => None,
+            Check::Incomplete(stack) => Some(
                 stack
                     .iter()
                     .rev()
                     .map(|delim| delim.ty.autocomplete_score())
                     .fold(0, |acc, score| 5 * acc + score),
-            );
-        }
-    }
+            ),
+        })
+        .collect();
     assert_eq!(
         scores.len() % 2,
         1,
@@ -172,9 +179,33 @@ fn part_two(lines: &[&[Delimiter]]) -> i64 {
     scores[scores.len() / 2]
 }
 
-pub(crate) fn solve() -> (i64, i64) {
-    let input = input();
-    let delimiters: Vec<_> = input.lines().map(to_delimiters).collect();
-    let delimiter_refs: Vec<_> = delimiters.iter().map(Vec::as_slice).collect();
-    (part_one(&delimiter_refs), part_two(&delimiter_refs))
+/// Parses each line into its sequence of delimiters.
+pub fn parse(input: &str) -> error::Result<Vec<Vec<Delimiter>>> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| to_delimiters(i, line))
+        .collect()
+}
+
+pub struct Day10;
+
+impl Solver for Day10 {
+    const DAY: u8 = 10;
+    const NAME: &'static str = "Syntax Scoring";
+    type Input = Vec<Vec<Delimiter>>;
+
+    fn parse(input: &str) -> error::Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part_one(delimiters: &Self::Input) -> Answer {
+        let delimiter_refs: Vec<_> = delimiters.iter().map(Vec::as_slice).collect();
+        part_one(&delimiter_refs).into()
+    }
+
+    fn part_two(delimiters: &Self::Input) -> Answer {
+        let delimiter_refs: Vec<_> = delimiters.iter().map(Vec::as_slice).collect();
+        part_two(&delimiter_refs).into()
+    }
 }
@@ -0,0 +1,159 @@
+use crate::answer::Answer;
+use crate::error::{Error, Result};
+use crate::solver::Solver;
+use std::collections::HashMap;
+
+pub type CaveId = usize;
+
+/// The most caves a system can have, as visited caves are tracked in a 64-bit set.
+const MAX_CAVES: usize = 64;
+
+/// The cave system, with cave names interned to indices into `names`, `small` and `edges`.
+#[derive(Clone, Debug)]
+pub struct Caves {
+    pub names: Vec<String>,
+    /// Whether each cave is small (lowercase), and so may only be visited a limited number of times.
+    pub small: Vec<bool>,
+    /// The caves adjacent to each cave.
+    pub edges: Vec<Vec<CaveId>>,
+    pub start: CaveId,
+    pub end: CaveId,
+}
+
+impl Caves {
+    /// Counts the paths from `start` to `end` that visit small caves at most once, except for at
+    /// most one small cave (other than `start` and `end`) which may be visited twice if
+    /// `allow_revisit` is set.
+    pub fn count_paths(&self, allow_revisit: bool) -> u64 {
+        let mut memo = HashMap::new();
+        self.count_from(self.start, 1 << self.start, allow_revisit, &mut memo)
+    }
+
+    fn count_from(
+        &self,
+        cave: CaveId,
+        visited: u64,
+        can_revisit: bool,
+        memo: &mut HashMap<(CaveId, u64, bool), u64>,
+    ) -> u64 {
+        if cave == self.end {
+            return 1;
+        }
+        if let Some(&n) = memo.get(&(cave, visited, can_revisit)) {
+            return n;
+        }
+        let mut paths = 0;
+        for &next in &self.edges[cave] {
+            if next == self.start {
+                continue;
+            }
+            if !self.small[next] {
+                paths += self.count_from(next, visited, can_revisit, memo);
+            } else if visited & (1 << next) == 0 {
+                paths += self.count_from(next, visited | 1 << next, can_revisit, memo);
+            } else if can_revisit && next != self.end {
+                paths += self.count_from(next, visited, false, memo);
+            }
+        }
+        memo.insert((cave, visited, can_revisit), paths);
+        paths
+    }
+}
+
+fn is_small(name: &str) -> bool {
+    name.chars().all(|c| c.is_ascii_lowercase())
+}
+
+pub fn parse(input: &str) -> Result<Caves> {
+    let mut ids: HashMap<&str, CaveId> = HashMap::new();
+    let mut names = Vec::new();
+    let mut edges: Vec<Vec<CaveId>> = Vec::new();
+    for (i, line) in input.lines().enumerate() {
+        let (a, b) = line
+            .split_once('-')
+            .ok_or_else(|| Error::line(i, line, "expected an edge `a-b`"))?;
+        for name in [a, b] {
+            if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphabetic()) {
+                return Err(Error::parse(i, line, name, "expected a cave name"));
+            }
+            if !is_small(name) && !name.chars().all(|c| c.is_ascii_uppercase()) {
+                return Err(Error::parse(
+                    i,
+                    line,
+                    name,
+                    "cave names must be all one case",
+                ));
+            }
+        }
+        if a == b {
+            return Err(Error::line(i, line, "a cave can't connect to itself"));
+        }
+        if !is_small(a) && !is_small(b) {
+            return Err(Error::line(
+                i,
+                line,
+                "adjacent big caves allow endless paths",
+            ));
+        }
+        let mut intern = |name| match ids.get(name) {
+            Some(&id) => Ok(id),
+            None if names.len() == MAX_CAVES => {
+                let reason = format!("at most {} caves are supported", MAX_CAVES);
+                Err(Error::parse(i, line, name, reason))
+            }
+            None => {
+                ids.insert(name, names.len());
+                names.push(name.to_string());
+                edges.push(Vec::new());
+                Ok(names.len() - 1)
+            }
+        };
+        let (a, b) = (intern(a)?, intern(b)?);
+        edges[a].push(b);
+        edges[b].push(a);
+    }
+    let find = |name: &str| {
+        ids.get(name).copied().ok_or_else(|| {
+            let last = input.lines().count().saturating_sub(1);
+            let line = input.lines().last().unwrap_or_default();
+            Error::line(last, line, format!("no `{}` cave", name))
+        })
+    };
+    let (start, end) = (find("start")?, find("end")?);
+    let small = names.iter().map(|n| is_small(n)).collect();
+    Ok(Caves {
+        names,
+        small,
+        edges,
+        start,
+        end,
+    })
+}
+
+pub fn part_one(caves: &Caves) -> u64 {
+    caves.count_paths(false)
+}
+
+pub fn part_two(caves: &Caves) -> u64 {
+    caves.count_paths(true)
+}
+
+pub struct Day12;
+
+impl Solver for Day12 {
+    const DAY: u8 = 12;
+    const NAME: &'static str = "Passage Pathing";
+    type Input = Caves;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part_one(caves: &Self::Input) -> Answer {
+        part_one(caves).into()
+    }
+
+    fn part_two(caves: &Self::Input) -> Answer {
+        part_two(caves).into()
+    }
+}
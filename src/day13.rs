@@ -0,0 +1,260 @@
+use crate::answer::Answer;
+use crate::error::{Error, Result};
+use crate::solver::Solver;
+use std::collections::BTreeSet;
+
+pub type Dot = (u32, u32);
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Fold {
+    /// Fold the right half of the paper leftwards along `x = n`.
+    X(u32),
+    /// Fold the bottom half of the paper upwards along `y = n`.
+    Y(u32),
+}
+
+/// Reflects `v` across `n`, if it lies beyond it. Values more than `2 * n` can't be reflected onto
+/// the paper, which `parse` rules out.
+fn reflect(v: u32, n: u32) -> u32 {
+    if v > n {
+        n - (v - n)
+    } else {
+        v
+    }
+}
+
+impl Fold {
+    fn apply(&self, (x, y): Dot) -> Dot {
+        match *self {
+            Fold::X(n) => (reflect(x, n), y),
+            Fold::Y(n) => (x, reflect(y, n)),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Manual {
+    pub dots: Vec<Dot>,
+    pub folds: Vec<Fold>,
+}
+
+/// Folds `dots` along `fold`, merging any dots that land on top of each other.
+pub fn fold(dots: &[Dot], fold: Fold) -> Vec<Dot> {
+    let mut folded: Vec<Dot> = dots.iter().map(|&dot| fold.apply(dot)).collect();
+    folded.sort_unstable();
+    folded.dedup();
+    folded
+}
+
+/// Draws `dots` as rows of `#` for dots and `.` for empty space.
+pub fn render(dots: &[Dot]) -> Vec<String> {
+    let width = dots.iter().map(|&(x, _)| x + 1).max().unwrap_or(0) as usize;
+    let height = dots.iter().map(|&(_, y)| y + 1).max().unwrap_or(0) as usize;
+    let mut rows = vec![vec![b'.'; width]; height];
+    for &(x, y) in dots {
+        rows[y as usize][x as usize] = b'#';
+    }
+    rows.into_iter()
+        .map(|row| String::from_utf8(row).expect("Rows are ASCII"))
+        .collect()
+}
+
+const LETTER_WIDTH: usize = 4;
+const LETTER_HEIGHT: usize = 6;
+
+/// The letters of the font the puzzle draws its codes in, each as its six rows of four pixels.
+const FONT: [(char, [&str; LETTER_HEIGHT]); 16] = [
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+/// Reads the letters drawn by [`render`], if every one of them is in the puzzle's font.
+pub fn ocr(rows: &[String]) -> Option<String> {
+    if rows.len() != LETTER_HEIGHT {
+        return None;
+    }
+    let width = rows.iter().map(String::len).max()?;
+    // Letters are separated by a blank column, which may be trimmed off after the last letter.
+    (0..width)
+        .step_by(LETTER_WIDTH + 1)
+        .map(|start| {
+            let glyph: Vec<String> = rows
+                .iter()
+                .map(|row| {
+                    let mut cells: String = row.chars().skip(start).take(LETTER_WIDTH).collect();
+                    while cells.len() < LETTER_WIDTH {
+                        cells.push('.');
+                    }
+                    cells
+                })
+                .collect();
+            FONT.iter()
+                .find(|(_, pixels)| pixels.iter().zip(&glyph).all(|(p, g)| p == g))
+                .map(|&(letter, _)| letter)
+        })
+        .collect()
+}
+
+pub fn parse(input: &str) -> Result<Manual> {
+    let mut dots = Vec::new();
+    let mut folds = Vec::new();
+    let mut lines = input.lines().enumerate();
+    for (i, line) in lines.by_ref() {
+        if line.is_empty() {
+            break;
+        }
+        let (x, y) = line
+            .split_once(',')
+            .ok_or_else(|| Error::line(i, line, "expected a dot `x,y`"))?;
+        let coordinate = |s: &str| s.parse().map_err(|e| Error::parse(i, line, s, e));
+        dots.push((coordinate(x)?, coordinate(y)?));
+    }
+    // The distinct coordinates on each axis, as folded so far, to check each fold keeps every dot
+    // on the paper.
+    let mut xs: BTreeSet<u32> = dots.iter().map(|&(x, _)| x).collect();
+    let mut ys: BTreeSet<u32> = dots.iter().map(|&(_, y)| y).collect();
+    for (i, line) in lines {
+        let instruction = line
+            .strip_prefix("fold along ")
+            .ok_or_else(|| Error::line(i, line, "expected `fold along x=n` or `fold along y=n`"))?;
+        let (axis, n) = instruction
+            .split_once('=')
+            .ok_or_else(|| Error::parse(i, line, instruction, "expected `x=n` or `y=n`"))?;
+        let token = n;
+        let n: u32 = n.parse().map_err(|e| Error::parse(i, line, token, e))?;
+        let (fold, coordinates) = match axis {
+            "x" => (Fold::X(n), &mut xs),
+            "y" => (Fold::Y(n), &mut ys),
+            _ => return Err(Error::parse(i, line, axis, "expected axis x or y")),
+        };
+        if coordinates
+            .last()
+            .is_some_and(|&max| max > n && max - n > n)
+        {
+            return Err(Error::parse(
+                i,
+                line,
+                token,
+                "folding here would put dots off the edge of the paper",
+            ));
+        }
+        *coordinates = coordinates.iter().map(|&v| reflect(v, n)).collect();
+        folds.push(fold);
+    }
+    Ok(Manual { dots, folds })
+}
+
+pub fn part_one(manual: &Manual) -> usize {
+    match manual.folds.first() {
+        Some(&first) => fold(&manual.dots, first).len(),
+        None => manual.dots.len(),
+    }
+}
+
+/// Renders the code left by making every fold.
+pub fn part_two(manual: &Manual) -> Vec<String> {
+    let dots = manual
+        .folds
+        .iter()
+        .fold(manual.dots.clone(), |dots, &f| fold(&dots, f));
+    render(&dots)
+}
+
+pub struct Day13;
+
+impl Solver for Day13 {
+    const DAY: u8 = 13;
+    const NAME: &'static str = "Transparent Origami";
+    type Input = Manual;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part_one(manual: &Self::Input) -> Answer {
+        part_one(manual).into()
+    }
+
+    /// Reads the code as letters, or draws it if any of them aren't in the puzzle's font.
+    fn part_two(manual: &Self::Input) -> Answer {
+        let rows = part_two(manual);
+        match ocr(&rows) {
+            Some(letters) => Answer::Text(letters),
+            None => rows.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "6,10\n0,14\n9,10\n0,3\n10,4\n4,11\n6,0\n6,12\n4,1\n0,13\n10,12\n3,4\n\
+                           3,0\n8,4\n1,10\n2,14\n8,10\n9,0\n\nfold along y=7\nfold along x=5\n";
+
+    /// The dots drawing `letters` in the puzzle's font, spaced one column apart.
+    fn dots_for(letters: &str) -> Vec<Dot> {
+        let mut dots = Vec::new();
+        for (i, letter) in letters.chars().enumerate() {
+            let (_, pixels) = FONT.iter().find(|(l, _)| *l == letter).unwrap();
+            for (y, row) in pixels.iter().enumerate() {
+                for (x, pixel) in row.chars().enumerate() {
+                    if pixel == '#' {
+                        dots.push(((i * (LETTER_WIDTH + 1) + x) as u32, y as u32));
+                    }
+                }
+            }
+        }
+        dots
+    }
+
+    #[test]
+    fn folds_the_example() {
+        let manual = parse(EXAMPLE).unwrap();
+        assert_eq!(part_one(&manual), 17);
+        let square = ["#####", "#...#", "#...#", "#...#", "#####"];
+        assert_eq!(part_two(&manual), square.map(String::from));
+        assert!(matches!(Day13::part_two(&manual), Answer::Lines(_)));
+    }
+
+    #[test]
+    fn reads_rendered_codes() {
+        let rows = render(&dots_for("EZJK"));
+        assert_eq!(ocr(&rows), Some(String::from("EZJK")));
+        // Mirroring the code along `x = 20` before folding it back leaves it readable.
+        let mirrored: Vec<Dot> = dots_for("EZJK").iter().map(|&(x, y)| (40 - x, y)).collect();
+        let manual = Manual {
+            dots: mirrored,
+            folds: vec![Fold::X(20)],
+        };
+        assert_eq!(Day13::part_two(&manual), Answer::Text(String::from("EZJK")));
+    }
+
+    #[test]
+    fn rejects_unknown_glyphs() {
+        let mut rows = render(&dots_for("AB"));
+        rows[0].replace_range(0..1, "#");
+        assert_eq!(ocr(&rows), None);
+        assert_eq!(ocr(&rows[1..]), None);
+    }
+
+    #[test]
+    fn rejects_folds_past_the_origin() {
+        assert!(parse("0,0\n20,0\n\nfold along x=5").is_err());
+        assert!(parse("0,0\n10,0\n\nfold along x=5").is_ok());
+    }
+}
@@ -0,0 +1,125 @@
+use crate::answer::Answer;
+use crate::error::{Error, Result};
+use crate::solver::Solver;
+
+/// The number of possible elements, `A` to `Z`.
+const ELEMENTS: usize = 26;
+
+/// An element, as its index from `A`.
+pub type Element = u8;
+
+/// A pair of adjacent elements, as an index into the pair buckets.
+fn pair(a: Element, b: Element) -> usize {
+    a as usize * ELEMENTS + b as usize
+}
+
+#[derive(Clone, Debug)]
+pub struct Polymer {
+    pub template: Vec<Element>,
+    /// The element inserted between each pair of elements, if any.
+    pub rules: Vec<Option<Element>>,
+}
+
+impl Polymer {
+    /// The number of each pair of adjacent elements in the template.
+    fn pair_counts(&self) -> Vec<u64> {
+        let mut counts = vec![0; ELEMENTS * ELEMENTS];
+        for w in self.template.windows(2) {
+            counts[pair(w[0], w[1])] += 1;
+        }
+        counts
+    }
+
+    /// Counts each element after `steps` rounds of pair insertion.
+    pub fn simulate(&self, steps: usize) -> [u64; ELEMENTS] {
+        let mut pairs = self.pair_counts();
+        for _ in 0..steps {
+            let mut next = vec![0; pairs.len()];
+            for (i, &n) in pairs.iter().enumerate().filter(|&(_, &n)| n > 0) {
+                let (a, b) = ((i / ELEMENTS) as Element, (i % ELEMENTS) as Element);
+                match self.rules[i] {
+                    Some(c) => {
+                        next[pair(a, c)] += n;
+                        next[pair(c, b)] += n;
+                    }
+                    None => next[i] += n,
+                }
+            }
+            pairs = next;
+        }
+        // Every element starts exactly one pair except the last, which never changes.
+        let mut elements = [0; ELEMENTS];
+        for (i, &n) in pairs.iter().enumerate() {
+            elements[i / ELEMENTS] += n;
+        }
+        if let Some(&last) = self.template.last() {
+            elements[last as usize] += 1;
+        }
+        elements
+    }
+
+    /// The difference between the most and least common elements after `steps` steps.
+    pub fn spread(&self, steps: usize) -> u64 {
+        let counts = self.simulate(steps);
+        let present = counts.iter().copied().filter(|&n| n > 0);
+        present.clone().max().unwrap_or(0) - present.min().unwrap_or(0)
+    }
+}
+
+fn parse_elements(line_index: usize, line: &str, s: &str) -> Result<Vec<Element>> {
+    s.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' => Ok(b - b'A'),
+            _ => Err(Error::parse(line_index, line, s, "expected elements A-Z")),
+        })
+        .collect()
+}
+
+pub fn parse(input: &str) -> Result<Polymer> {
+    let mut lines = input.lines().enumerate();
+    let (_, line) = lines.next().unwrap_or((0, ""));
+    let template = parse_elements(0, line, line.trim())?;
+    if template.is_empty() {
+        return Err(Error::line(0, line, "expected a polymer template"));
+    }
+    let mut rules = vec![None; ELEMENTS * ELEMENTS];
+    for (i, line) in lines.filter(|(_, line)| !line.is_empty()) {
+        let (from, to) = line
+            .split_once(" -> ")
+            .ok_or_else(|| Error::line(i, line, "expected a rule `AB -> C`"))?;
+        let (from, to) = (parse_elements(i, line, from)?, parse_elements(i, line, to)?);
+        let (&[a, b], &[c]) = (from.as_slice(), to.as_slice()) else {
+            return Err(Error::line(i, line, "expected a rule `AB -> C`"));
+        };
+        rules[pair(a, b)] = Some(c);
+    }
+    Ok(Polymer { template, rules })
+}
+
+pub fn part_one(polymer: &Polymer) -> u64 {
+    polymer.spread(10)
+}
+
+pub fn part_two(polymer: &Polymer) -> u64 {
+    polymer.spread(40)
+}
+
+pub struct Day14;
+
+impl Solver for Day14 {
+    const DAY: u8 = 14;
+    const NAME: &'static str = "Extended Polymerization";
+    type Input = Polymer;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part_one(polymer: &Self::Input) -> Answer {
+        part_one(polymer).into()
+    }
+
+    fn part_two(polymer: &Self::Input) -> Answer {
+        part_two(polymer).into()
+    }
+}
@@ -0,0 +1,100 @@
+use crate::answer::Answer;
+use crate::error::{Error, Result};
+use crate::solver::Solver;
+use crate::utils::{parse_digits, parse_grid, Grid};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// Repeats `grid` `times` times in each direction, adding one to each risk per tile right or down
+/// and wrapping risks above 9 back around to 1.
+pub fn tile(grid: &Grid<u8>, times: usize) -> Grid<u8> {
+    let (width, height) = (grid.width() * times, grid.height() * times);
+    let cells = (0..height)
+        .flat_map(|r| (0..width).map(move |c| (r, c)))
+        .map(|(r, c)| {
+            let base = grid[(r % grid.height(), c % grid.width())] as usize;
+            let shift = r / grid.height() + c / grid.width();
+            ((base + shift + 8) % 9 + 1) as u8
+        })
+        .collect();
+    Grid::new(width, height, cells)
+}
+
+/// The lowest total risk of any path from the top left to the bottom right, using Dijkstra's
+/// algorithm. The risk of the starting position isn't counted.
+pub fn lowest_risk(grid: &Grid<u8>) -> u64 {
+    if grid.width() == 0 || grid.height() == 0 {
+        return 0;
+    }
+    let target = (grid.height() - 1, grid.width() - 1);
+    let mut best = Grid::new(
+        grid.width(),
+        grid.height(),
+        vec![u64::MAX; grid.width() * grid.height()],
+    );
+    let mut queue = BinaryHeap::from([Reverse((0, (0, 0)))]);
+    while let Some(Reverse((risk, position))) = queue.pop() {
+        if position == target {
+            return risk;
+        }
+        if risk > best[position] {
+            continue;
+        }
+        for next in grid.neighbors4(position) {
+            let next_risk = risk + grid[next] as u64;
+            if next_risk < best[next] {
+                best[next] = next_risk;
+                queue.push(Reverse((next_risk, next)));
+            }
+        }
+    }
+    unreachable!("Every position in a grid is reachable")
+}
+
+fn parse_risks(line_index: usize, line: &str) -> Result<Vec<u8>> {
+    let risks = parse_digits(line_index, line)?;
+    // Risk levels wrap from 9 back to 1 when tiled, so a 0 wouldn't survive tiling.
+    if let Some(i) = risks.iter().position(|&risk| risk == 0) {
+        let token = &line[i..i + 1];
+        return Err(Error::parse(
+            line_index,
+            line,
+            token,
+            "risk levels must be 1 to 9",
+        ));
+    }
+    Ok(risks)
+}
+
+/// Parses the grid of risk levels, each a single digit from 1 to 9.
+pub fn parse(input: &str) -> Result<Grid<u8>> {
+    parse_grid(input, parse_risks)
+}
+
+pub fn part_one(grid: &Grid<u8>) -> u64 {
+    lowest_risk(grid)
+}
+
+pub fn part_two(grid: &Grid<u8>) -> u64 {
+    lowest_risk(&tile(grid, 5))
+}
+
+pub struct Day15;
+
+impl Solver for Day15 {
+    const DAY: u8 = 15;
+    const NAME: &'static str = "Chiton";
+    type Input = Grid<u8>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part_one(grid: &Self::Input) -> Answer {
+        part_one(grid).into()
+    }
+
+    fn part_two(grid: &Self::Input) -> Answer {
+        part_two(grid).into()
+    }
+}
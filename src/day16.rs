@@ -0,0 +1,347 @@
+use crate::answer::Answer;
+use crate::error::{Error, Result};
+use crate::solver::Solver;
+use std::fmt::{Display, Formatter};
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Operator {
+    Sum,
+    Product,
+    Minimum,
+    Maximum,
+    GreaterThan,
+    LessThan,
+    EqualTo,
+}
+
+impl Operator {
+    fn from_type_id(type_id: u64) -> Option<Self> {
+        Some(match type_id {
+            0 => Operator::Sum,
+            1 => Operator::Product,
+            2 => Operator::Minimum,
+            3 => Operator::Maximum,
+            5 => Operator::GreaterThan,
+            6 => Operator::LessThan,
+            7 => Operator::EqualTo,
+            _ => return None,
+        })
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Operator::Sum => "sum",
+            Operator::Product => "product",
+            Operator::Minimum => "min",
+            Operator::Maximum => "max",
+            Operator::GreaterThan => "gt",
+            Operator::LessThan => "lt",
+            Operator::EqualTo => "eq",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Body {
+    Literal(u64),
+    Operator(Operator, Vec<Packet>),
+}
+
+/// A decoded BITS packet.
+///
+/// Displays as an s-expression such as `(sum 1 (product 2 3))`. The alternate form, `{:#}`, shows
+/// one packet per line, indented by depth, along with each packet's version.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Packet {
+    pub version: u8,
+    pub body: Body,
+}
+
+impl Packet {
+    /// The sum of the versions of this packet and all the packets inside it.
+    pub fn version_sum(&self) -> u64 {
+        let inner = match &self.body {
+            Body::Literal(_) => 0,
+            Body::Operator(_, packets) => packets.iter().map(Packet::version_sum).sum(),
+        };
+        self.version as u64 + inner
+    }
+
+    /// The value of the expression this packet represents, or `None` if an operator has the wrong
+    /// number of sub-packets or a value overflows a `u64`.
+    pub fn evaluate(&self) -> Option<u64> {
+        let (op, packets) = match &self.body {
+            Body::Literal(value) => return Some(*value),
+            Body::Operator(op, packets) => (op, packets),
+        };
+        let values: Vec<u64> = packets
+            .iter()
+            .map(Packet::evaluate)
+            .collect::<Option<_>>()?;
+        let compare = |f: fn(&u64, &u64) -> bool| match values[..] {
+            [a, b] => Some(f(&a, &b) as u64),
+            _ => None,
+        };
+        match op {
+            Operator::Sum => values.iter().try_fold(0, |acc: u64, &v| acc.checked_add(v)),
+            Operator::Product => values.iter().try_fold(1, |acc: u64, &v| acc.checked_mul(v)),
+            Operator::Minimum => values.iter().copied().min(),
+            Operator::Maximum => values.iter().copied().max(),
+            Operator::GreaterThan => compare(u64::gt),
+            Operator::LessThan => compare(u64::lt),
+            Operator::EqualTo => compare(u64::eq),
+        }
+    }
+
+    fn fmt_tree(&self, f: &mut Formatter<'_>, depth: usize) -> std::fmt::Result {
+        write!(f, "{:indent$}v{} ", "", self.version, indent = 2 * depth)?;
+        match &self.body {
+            Body::Literal(value) => write!(f, "{}", value),
+            Body::Operator(op, packets) => {
+                write!(f, "{}", op.name())?;
+                for packet in packets {
+                    writeln!(f)?;
+                    packet.fmt_tree(f, depth + 1)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Display for Packet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if f.alternate() {
+            return self.fmt_tree(f, 0);
+        }
+        match &self.body {
+            Body::Literal(value) => write!(f, "{}", value),
+            Body::Operator(op, packets) => {
+                write!(f, "({}", op.name())?;
+                for packet in packets {
+                    write!(f, " {}", packet)?;
+                }
+                write!(f, ")")
+            }
+        }
+    }
+}
+
+/// Reads a transmission bit by bit, most significant bit of each hex digit first.
+struct Bits<'a> {
+    line: &'a str,
+    digits: Vec<u8>,
+    pos: usize,
+}
+
+impl<'a> Bits<'a> {
+    fn new(line: &'a str) -> Result<Self> {
+        let digits = line
+            .char_indices()
+            .map(|(i, ch)| {
+                ch.to_digit(16).map(|d| d as u8).ok_or_else(|| {
+                    let token = &line[i..i + ch.len_utf8()];
+                    Error::parse(0, line, token, "expected a hex digit")
+                })
+            })
+            .collect::<Result<_>>()?;
+        Ok(Bits {
+            line,
+            digits,
+            pos: 0,
+        })
+    }
+
+    /// Reports a problem with the packet at bit `pos`, pointing at the hex digit it starts in.
+    fn error(&self, pos: usize, reason: &str) -> Error {
+        let start = (pos / 4).min(self.line.len());
+        let end = (start + 1).min(self.line.len());
+        Error::parse(0, self.line, &self.line[start..end], reason)
+    }
+
+    fn read(&mut self, n: usize) -> Result<u64> {
+        if self.pos + n > 4 * self.digits.len() {
+            return Err(self.error(self.pos, "transmission ends part way through a packet"));
+        }
+        let value = (self.pos..self.pos + n).fold(0, |acc, i| {
+            let bit = self.digits[i / 4] >> (3 - i % 4) & 1;
+            acc << 1 | bit as u64
+        });
+        self.pos += n;
+        Ok(value)
+    }
+
+    fn packet(&mut self) -> Result<Packet> {
+        let start = self.pos;
+        let version = self.read(3)? as u8;
+        let type_id = self.read(3)?;
+        let Some(op) = Operator::from_type_id(type_id) else {
+            return Ok(Packet {
+                version,
+                body: Body::Literal(self.literal(start)?),
+            });
+        };
+        let mut packets = Vec::new();
+        if self.read(1)? == 0 {
+            let length = self.read(15)? as usize;
+            let end = self.pos + length;
+            while self.pos < end {
+                packets.push(self.packet()?);
+            }
+            if self.pos != end {
+                return Err(self.error(start, "sub-packets overrun their stated length"));
+            }
+        } else {
+            for _ in 0..self.read(11)? {
+                packets.push(self.packet()?);
+            }
+        }
+        let arity_ok = match op {
+            Operator::Sum | Operator::Product => true,
+            Operator::Minimum | Operator::Maximum => !packets.is_empty(),
+            Operator::GreaterThan | Operator::LessThan | Operator::EqualTo => packets.len() == 2,
+        };
+        if !arity_ok {
+            return Err(self.error(start, "wrong number of sub-packets for the operator"));
+        }
+        Ok(Packet {
+            version,
+            body: Body::Operator(op, packets),
+        })
+    }
+
+    fn literal(&mut self, start: usize) -> Result<u64> {
+        let mut value: u64 = 0;
+        loop {
+            let group = self.read(5)?;
+            if value.leading_zeros() < 4 {
+                return Err(self.error(start, "literal value doesn't fit in 64 bits"));
+            }
+            value = value << 4 | (group & 0xf);
+            if group & 0x10 == 0 {
+                return Ok(value);
+            }
+        }
+    }
+}
+
+/// Decodes the hex transmission into its outermost packet, ignoring any trailing padding.
+pub fn parse(input: &str) -> Result<Packet> {
+    let line = input.lines().next().unwrap_or_default().trim();
+    Bits::new(line)?.packet()
+}
+
+pub fn part_one(packet: &Packet) -> u64 {
+    packet.version_sum()
+}
+
+pub fn part_two(packet: &Packet) -> Option<u64> {
+    packet.evaluate()
+}
+
+pub struct Day16;
+
+impl Solver for Day16 {
+    const DAY: u8 = 16;
+    const NAME: &'static str = "Packet Decoder";
+    type Input = Packet;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part_one(packet: &Self::Input) -> Answer {
+        part_one(packet).into()
+    }
+
+    fn part_two(packet: &Self::Input) -> Answer {
+        Answer::or_text(part_two(packet), "overflows a 64-bit integer")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn literal(version: u8, value: u64) -> Packet {
+        Packet {
+            version,
+            body: Body::Literal(value),
+        }
+    }
+
+    #[test]
+    fn decodes_literals() {
+        assert_eq!(parse("D2FE28").unwrap(), literal(6, 2021));
+    }
+
+    #[test]
+    fn decodes_both_length_types() {
+        let by_length = parse("38006F45291200").unwrap();
+        let expected = Body::Operator(Operator::LessThan, vec![literal(6, 10), literal(2, 20)]);
+        assert_eq!((by_length.version, by_length.body), (1, expected));
+        let by_count = parse("EE00D40C823060").unwrap();
+        let subpackets = vec![literal(2, 1), literal(4, 2), literal(1, 3)];
+        let expected = Body::Operator(Operator::Maximum, subpackets);
+        assert_eq!((by_count.version, by_count.body), (7, expected));
+    }
+
+    #[test]
+    fn sums_versions() {
+        for (hex, sum) in [
+            ("8A004A801A8002F478", 16),
+            ("620080001611562C8802118E34", 12),
+            ("C0015000016115A2E0802F182340", 23),
+            ("A0016C880162017C3686B18A3D4780", 31),
+        ] {
+            assert_eq!(part_one(&parse(hex).unwrap()), sum, "{}", hex);
+        }
+    }
+
+    #[test]
+    fn evaluates_expressions() {
+        for (hex, value) in [
+            ("C200B40A82", 3),
+            ("04005AC33890", 54),
+            ("880086C3E88112", 7),
+            ("CE00C43D881120", 9),
+            ("D8005AC2A8F0", 1),
+            ("F600BC2D8F", 0),
+            ("9C005AC2F8F0", 0),
+            ("9C0141080250320F1802104A08", 1),
+        ] {
+            assert_eq!(part_two(&parse(hex).unwrap()), Some(value), "{}", hex);
+        }
+    }
+
+    #[test]
+    fn limits_literals_to_64_bits() {
+        assert_eq!(
+            parse("13FFFFFFFFFFFFFFFFFFBC").unwrap(),
+            literal(0, u64::MAX)
+        );
+        assert!(parse("13FFFFFFFFFFFFFFFFFFFDE").is_err());
+    }
+
+    #[test]
+    fn rejects_overflow_and_wrong_arity() {
+        // The sum of two literals that are both `u64::MAX`.
+        let sum = parse("020084FFFFFFFFFFFFFFFFFFEF13FFFFFFFFFFFFFFFFFFBC").unwrap();
+        assert_eq!(part_two(&sum), None);
+        let comparison = Packet {
+            version: 0,
+            body: Body::Operator(Operator::GreaterThan, vec![literal(0, 1)]),
+        };
+        assert_eq!(comparison.evaluate(), None);
+    }
+
+    #[test]
+    fn displays_packets() {
+        let packet = parse("9C0141080250320F1802104A08").unwrap();
+        assert_eq!(packet.to_string(), "(eq (sum 1 3) (product 2 2))");
+        assert_eq!(
+            format!("{:#}", parse("C200B40A82").unwrap()),
+            "v6 sum\n  v6 1\n  v2 2"
+        );
+    }
+}
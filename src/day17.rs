@@ -0,0 +1,187 @@
+use crate::answer::Answer;
+use crate::error::{Error, Result};
+use crate::solver::Solver;
+use std::ops::RangeInclusive;
+
+pub type Velocity = (i64, i64);
+
+/// The target area, which must not include the launcher's height of `y = 0`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Target {
+    pub x: RangeInclusive<i64>,
+    pub y: RangeInclusive<i64>,
+}
+
+impl Target {
+    /// The initial horizontal velocities that could reach the target at all.
+    ///
+    /// Anything faster than the far edge overshoots on the first step, and anything slower than
+    /// the smallest `v` with `v + (v - 1) + ... + 1` reaching the near edge stalls short of it.
+    fn vx_range(&self) -> RangeInclusive<i64> {
+        let (&x1, &x2) = (self.x.start(), self.x.end());
+        let slowest = |near: i64| (0..).find(|v| v * (v + 1) / 2 >= near).unwrap();
+        if x1 > 0 {
+            slowest(x1)..=x2
+        } else if x2 < 0 {
+            x1..=-slowest(-x2)
+        } else {
+            x1..=x2
+        }
+    }
+
+    /// The initial vertical velocities that could reach the target at all.
+    ///
+    /// Shooting downwards faster than the bottom edge overshoots on the first step. Shooting
+    /// upwards, the probe passes back through the same heights on its way down, arriving at
+    /// `y = 0` at `-(vy + 1)`, so it skips a target below if that's faster than the bottom edge and
+    /// one above if its first step is already higher than the top edge.
+    fn vy_range(&self) -> RangeInclusive<i64> {
+        let (&y1, &y2) = (self.y.start(), self.y.end());
+        if y2 < 0 {
+            y1..=-y1 - 1
+        } else {
+            0..=y2
+        }
+    }
+
+    /// The highest point reached by a probe launched at `velocity`, if it ever lands in the target.
+    pub fn peak_if_hit(&self, (mut vx, mut vy): Velocity) -> Option<i64> {
+        let (mut x, mut y, mut peak) = (0, 0, 0);
+        // Once below the target and falling, the probe can never come back up.
+        while vy >= 0 || y >= *self.y.start() {
+            x += vx;
+            y += vy;
+            peak = peak.max(y);
+            vx -= vx.signum();
+            vy -= 1;
+            if self.x.contains(&x) && self.y.contains(&y) {
+                return Some(peak);
+            }
+        }
+        None
+    }
+
+    /// Every initial velocity that lands the probe in the target, with the peak height it reaches.
+    pub fn hits(&self) -> impl Iterator<Item = (Velocity, i64)> + '_ {
+        self.vx_range()
+            .flat_map(move |vx| self.vy_range().map(move |vy| (vx, vy)))
+            .filter_map(move |v| self.peak_if_hit(v).map(|peak| (v, peak)))
+    }
+}
+
+fn parse_range<'a>(line: &'a str, s: &'a str, axis: &str) -> Result<RangeInclusive<i64>> {
+    let (name, range) = s
+        .trim()
+        .split_once('=')
+        .ok_or_else(|| Error::parse(0, line, s, "expected `axis=min..max`"))?;
+    if name != axis {
+        return Err(Error::parse(
+            0,
+            line,
+            name,
+            format!("expected axis {}", axis),
+        ));
+    }
+    let (a, b) = range
+        .split_once("..")
+        .ok_or_else(|| Error::parse(0, line, range, "expected `min..max`"))?;
+    let bound = |t: &str| t.parse::<i64>().map_err(|e| Error::parse(0, line, t, e));
+    let (a, b) = (bound(a)?, bound(b)?);
+    Ok(a.min(b)..=a.max(b))
+}
+
+pub fn parse(input: &str) -> Result<Target> {
+    let line = input.lines().next().unwrap_or_default();
+    let area = line
+        .strip_prefix("target area: ")
+        .ok_or_else(|| Error::line(0, line, "expected `target area: x=..., y=...`"))?;
+    let (x, y) = area
+        .split_once(',')
+        .ok_or_else(|| Error::parse(0, line, area, "expected `x=..., y=...`"))?;
+    let target = Target {
+        x: parse_range(line, x, "x")?,
+        y: parse_range(line, y, "y")?,
+    };
+    if target.y.contains(&0) {
+        return Err(Error::parse(
+            0,
+            line,
+            y,
+            "targets spanning the launcher's height can be hit by endlessly many velocities",
+        ));
+    }
+    Ok(target)
+}
+
+pub fn part_one(target: &Target) -> i64 {
+    target.hits().map(|(_, peak)| peak).max().unwrap_or(0)
+}
+
+pub fn part_two(target: &Target) -> usize {
+    target.hits().count()
+}
+
+pub struct Day17;
+
+impl Solver for Day17 {
+    const DAY: u8 = 17;
+    const NAME: &'static str = "Trick Shot";
+    type Input = Target;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part_one(target: &Self::Input) -> Answer {
+        part_one(target).into()
+    }
+
+    fn part_two(target: &Self::Input) -> Answer {
+        part_two(target).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tries every velocity in a generous square, as a check on the bounds `hits` searches.
+    fn brute_force(target: &Target) -> (i64, usize) {
+        let hits: Vec<i64> = (-100..=100)
+            .flat_map(|vx| (-100..=100).map(move |vy| (vx, vy)))
+            .filter_map(|v| target.peak_if_hit(v))
+            .collect();
+        (hits.iter().copied().max().unwrap_or(0), hits.len())
+    }
+
+    fn check(input: &str, expected: (i64, usize)) {
+        let target = parse(input).unwrap();
+        assert_eq!(
+            (part_one(&target), part_two(&target)),
+            expected,
+            "{}",
+            input
+        );
+        assert_eq!(brute_force(&target), expected, "{}", input);
+    }
+
+    #[test]
+    fn solves_the_example() {
+        check("target area: x=20..30, y=-10..-5", (45, 112));
+    }
+
+    #[test]
+    fn solves_targets_behind_the_launcher() {
+        check("target area: x=-30..-20, y=-10..-5", (45, 112));
+    }
+
+    #[test]
+    fn solves_targets_above_the_launcher() {
+        check("target area: x=-5..5, y=3..8", (8, 73));
+    }
+
+    #[test]
+    fn rejects_targets_at_the_launchers_height() {
+        assert!(parse("target area: x=20..30, y=-10..5").is_err());
+    }
+}
@@ -0,0 +1,264 @@
+use crate::answer::Answer;
+use crate::error::Result;
+use crate::solver::Solver;
+use crate::utils::parse_input;
+use std::fmt::{Display, Formatter};
+use std::ops::Add;
+use std::str::FromStr;
+
+/// A snailfish number: either a regular number or a pair of snailfish numbers.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Number {
+    Regular(u32),
+    Pair(Box<Number>, Box<Number>),
+}
+
+#[derive(Debug)]
+pub struct ParseNumberError;
+
+impl Display for ParseNumberError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Failed to parse snailfish number")
+    }
+}
+
+impl std::error::Error for ParseNumberError {}
+
+impl FromStr for Number {
+    type Err = ParseNumberError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        type ParseResult<T> = std::result::Result<T, ParseNumberError>;
+        fn eat(s: &[u8], pos: &mut usize, b: u8) -> ParseResult<()> {
+            if s.get(*pos) != Some(&b) {
+                return Err(ParseNumberError);
+            }
+            *pos += 1;
+            Ok(())
+        }
+        fn number(s: &[u8], pos: &mut usize) -> ParseResult<Number> {
+            if eat(s, pos, b'[').is_ok() {
+                let left = number(s, pos)?;
+                eat(s, pos, b',')?;
+                let right = number(s, pos)?;
+                eat(s, pos, b']')?;
+                return Ok(Number::Pair(Box::new(left), Box::new(right)));
+            }
+            let digits = s[*pos..].iter().take_while(|b| b.is_ascii_digit()).count();
+            let value = std::str::from_utf8(&s[*pos..*pos + digits])
+                .ok()
+                .and_then(|d| d.parse().ok())
+                .ok_or(ParseNumberError)?;
+            *pos += digits;
+            Ok(Number::Regular(value))
+        }
+        let s = s.trim().as_bytes();
+        let mut pos = 0;
+        let parsed = number(s, &mut pos)?;
+        if pos != s.len() {
+            return Err(ParseNumberError);
+        }
+        Ok(parsed)
+    }
+}
+
+impl Display for Number {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Number::Regular(n) => write!(f, "{}", n),
+            Number::Pair(left, right) => write!(f, "[{},{}]", left, right),
+        }
+    }
+}
+
+impl Add for Number {
+    type Output = Number;
+
+    /// Pairs up the two numbers and reduces the result.
+    fn add(self, rhs: Number) -> Number {
+        let mut sum = Number::Pair(Box::new(self), Box::new(rhs));
+        sum.reduce();
+        sum
+    }
+}
+
+impl Number {
+    pub fn magnitude(&self) -> u64 {
+        match self {
+            Number::Regular(n) => *n as u64,
+            Number::Pair(left, right) => 3 * left.magnitude() + 2 * right.magnitude(),
+        }
+    }
+
+    /// Repeatedly explodes, or failing that splits, the leftmost eligible pair until neither
+    /// applies.
+    pub fn reduce(&mut self) {
+        while self.explode(0).is_some() || self.split() {}
+    }
+
+    /// Explodes the leftmost pair of regular numbers nested inside four pairs, returning the
+    /// values still to be added to the nearest regular numbers on its left and right.
+    fn explode(&mut self, depth: usize) -> Option<(Option<u32>, Option<u32>)> {
+        let Number::Pair(left, right) = self else {
+            return None;
+        };
+        if depth >= 4 {
+            if let (Number::Regular(a), Number::Regular(b)) = (&**left, &**right) {
+                let carry = (Some(*a), Some(*b));
+                *self = Number::Regular(0);
+                return Some(carry);
+            }
+        }
+        if let Some((a, b)) = left.explode(depth + 1) {
+            if let Some(b) = b {
+                right.add_leftmost(b);
+            }
+            return Some((a, None));
+        }
+        if let Some((a, b)) = right.explode(depth + 1) {
+            if let Some(a) = a {
+                left.add_rightmost(a);
+            }
+            return Some((None, b));
+        }
+        None
+    }
+
+    fn add_leftmost(&mut self, value: u32) {
+        match self {
+            Number::Regular(n) => *n += value,
+            Number::Pair(left, _) => left.add_leftmost(value),
+        }
+    }
+
+    fn add_rightmost(&mut self, value: u32) {
+        match self {
+            Number::Regular(n) => *n += value,
+            Number::Pair(_, right) => right.add_rightmost(value),
+        }
+    }
+
+    /// Splits the leftmost regular number of 10 or more into a pair, returning whether it did.
+    fn split(&mut self) -> bool {
+        match self {
+            Number::Regular(n) if *n >= 10 => {
+                let (a, b) = (*n / 2, *n - *n / 2);
+                *self = Number::Pair(Box::new(Number::Regular(a)), Box::new(Number::Regular(b)));
+                true
+            }
+            Number::Regular(_) => false,
+            Number::Pair(left, right) => left.split() || right.split(),
+        }
+    }
+}
+
+pub fn parse(input: &str) -> Result<Vec<Number>> {
+    parse_input(input)
+}
+
+pub fn part_one(numbers: &[Number]) -> u64 {
+    numbers
+        .iter()
+        .cloned()
+        .reduce(|a, b| a + b)
+        .map_or(0, |sum| sum.magnitude())
+}
+
+/// The largest magnitude from adding any two different numbers, in either order.
+pub fn part_two(numbers: &[Number]) -> u64 {
+    (0..numbers.len())
+        .flat_map(|i| {
+            (0..numbers.len())
+                .filter(move |&j| j != i)
+                .map(move |j| (i, j))
+        })
+        .map(|(i, j)| (numbers[i].clone() + numbers[j].clone()).magnitude())
+        .max()
+        .unwrap_or(0)
+}
+
+pub struct Day18;
+
+impl Solver for Day18 {
+    const DAY: u8 = 18;
+    const NAME: &'static str = "Snailfish";
+    type Input = Vec<Number>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part_one(numbers: &Self::Input) -> Answer {
+        part_one(numbers).into()
+    }
+
+    fn part_two(numbers: &Self::Input) -> Answer {
+        part_two(numbers).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn number(s: &str) -> Number {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn round_trips_through_display() {
+        for s in [
+            "9",
+            "[1,2]",
+            "[[[[1,3],[5,3]],[[1,3],[8,7]]],[[[4,9],[6,9]],[[8,2],[7,3]]]]",
+        ] {
+            assert_eq!(number(s).to_string(), s);
+        }
+        assert!("[1,2".parse::<Number>().is_err());
+        assert!("[1,2]3".parse::<Number>().is_err());
+    }
+
+    #[test]
+    fn explodes_pairs() {
+        for (before, after) in [
+            ("[[[[[9,8],1],2],3],4]", "[[[[0,9],2],3],4]"),
+            ("[7,[6,[5,[4,[3,2]]]]]", "[7,[6,[5,[7,0]]]]"),
+            ("[[6,[5,[4,[3,2]]]],1]", "[[6,[5,[7,0]]],3]"),
+            (
+                "[[3,[2,[1,[7,3]]]],[6,[5,[4,[3,2]]]]]",
+                "[[3,[2,[8,0]]],[9,[5,[4,[3,2]]]]]",
+            ),
+            (
+                "[[3,[2,[8,0]]],[9,[5,[4,[3,2]]]]]",
+                "[[3,[2,[8,0]]],[9,[5,[7,0]]]]",
+            ),
+        ] {
+            let mut n = number(before);
+            assert!(n.explode(0).is_some());
+            assert_eq!(n.to_string(), after);
+        }
+    }
+
+    #[test]
+    fn splits_regular_numbers() {
+        let mut n = number("[[[[0,7],4],[15,[0,13]]],[1,1]]");
+        assert!(n.split());
+        assert_eq!(n.to_string(), "[[[[0,7],4],[[7,8],[0,13]]],[1,1]]");
+        assert!(n.split());
+        assert_eq!(n.to_string(), "[[[[0,7],4],[[7,8],[0,[6,7]]]],[1,1]]");
+        assert!(!number("[9,[1,2]]").split());
+    }
+
+    #[test]
+    fn reduces_sums() {
+        let sum = number("[[[[4,3],4],4],[7,[[8,4],9]]]") + number("[1,1]");
+        assert_eq!(sum.to_string(), "[[[[0,7],4],[[7,8],[6,0]]],[8,1]]");
+    }
+
+    #[test]
+    fn computes_magnitudes() {
+        assert_eq!(number("[[1,2],[[3,4],5]]").magnitude(), 143);
+        let n = number("[[[[8,7],[7,7]],[[8,6],[7,7]]],[[[0,7],[6,6]],[8,7]]]");
+        assert_eq!(n.magnitude(), 3488);
+    }
+}
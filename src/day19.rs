@@ -0,0 +1,243 @@
+use crate::answer::Answer;
+use crate::error::{Error, Result};
+use crate::solver::Solver;
+use std::collections::{HashMap, HashSet};
+
+/// The number of beacons two scanners must both see for their reports to be aligned.
+const MIN_OVERLAP: usize = 12;
+
+pub type Point = [i32; 3];
+
+/// A rotation of the axes: component `i` of a rotated point is `signs[i] * p[axes[i]]`.
+#[derive(Copy, Clone, Debug)]
+struct Rotation {
+    axes: [usize; 3],
+    signs: [i32; 3],
+}
+
+impl Rotation {
+    fn apply(&self, p: Point) -> Point {
+        [0, 1, 2].map(|i| self.signs[i] * p[self.axes[i]])
+    }
+}
+
+/// The 24 rotations: every signed permutation of the axes that keeps them right-handed.
+fn rotations() -> Vec<Rotation> {
+    let permutations = [
+        ([0, 1, 2], 1),
+        ([1, 2, 0], 1),
+        ([2, 0, 1], 1),
+        ([0, 2, 1], -1),
+        ([2, 1, 0], -1),
+        ([1, 0, 2], -1),
+    ];
+    let signs = (0..8).map(|bits| [0, 1, 2].map(|i| if bits >> i & 1 == 0 { 1 } else { -1 }));
+    permutations
+        .iter()
+        .flat_map(|&(axes, parity)| {
+            signs
+                .clone()
+                .filter(move |s: &[i32; 3]| s.iter().product::<i32>() == parity)
+                .map(move |signs| Rotation { axes, signs })
+        })
+        .collect()
+}
+
+fn add(a: Point, b: Point) -> Point {
+    [a[0] + b[0], a[1] + b[1], a[2] + b[2]]
+}
+
+fn sub(a: Point, b: Point) -> Point {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn manhattan(a: Point, b: Point) -> i64 {
+    sub(a, b).iter().map(|&d| (d as i64).abs()).sum()
+}
+
+/// The sorted squared distances between every pair of beacons in a report, which don't depend on
+/// the scanner's position or orientation.
+fn fingerprint(beacons: &[Point]) -> Vec<i64> {
+    let mut distances: Vec<i64> = beacons
+        .iter()
+        .enumerate()
+        .flat_map(|(i, &a)| beacons[i + 1..].iter().map(move |&b| (a, b)))
+        .map(|(a, b)| sub(a, b).iter().map(|&d| (d as i64).pow(2)).sum())
+        .collect();
+    distances.sort_unstable();
+    distances
+}
+
+/// Counts the distances two sorted fingerprints have in common.
+fn shared_distances(a: &[i64], b: &[i64]) -> usize {
+    let (mut i, mut j, mut shared) = (0, 0, 0);
+    while i < a.len() && j < b.len() {
+        match a[i].cmp(&b[j]) {
+            std::cmp::Ordering::Less => i += 1,
+            std::cmp::Ordering::Greater => j += 1,
+            std::cmp::Ordering::Equal => {
+                shared += 1;
+                i += 1;
+                j += 1;
+            }
+        }
+    }
+    shared
+}
+
+/// Finds the rotation and position that line `report` up with `known` beacons, returning the
+/// report's beacons in the same frame as `known` and the scanner's position in that frame.
+fn try_align(
+    known: &[Point],
+    report: &[Point],
+    rotations: &[Rotation],
+) -> Option<(Vec<Point>, Point)> {
+    for rotation in rotations {
+        let rotated: Vec<Point> = report.iter().map(|&p| rotation.apply(p)).collect();
+        let mut offsets: HashMap<Point, usize> = HashMap::new();
+        for &a in known {
+            for &b in &rotated {
+                let offset = sub(a, b);
+                let count = offsets.entry(offset).or_default();
+                *count += 1;
+                if *count >= MIN_OVERLAP {
+                    let beacons = rotated.iter().map(|&p| add(p, offset)).collect();
+                    return Some((beacons, offset));
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Every beacon and scanner, positioned relative to the first scanner.
+#[derive(Clone, Debug)]
+pub struct Map {
+    pub scanners: Vec<Point>,
+    pub beacons: HashSet<Point>,
+}
+
+/// Aligns every report with the first, or returns the index of a scanner that can't be aligned
+/// with any of the others.
+pub fn align(reports: &[Vec<Point>]) -> std::result::Result<Map, usize> {
+    let rotations = rotations();
+    let fingerprints: Vec<Vec<i64>> = reports.iter().map(|r| fingerprint(r)).collect();
+    // Twelve shared beacons make this many shared pairwise distances.
+    let min_shared = MIN_OVERLAP * (MIN_OVERLAP - 1) / 2;
+    let mut aligned: Vec<Option<(Vec<Point>, Point)>> = vec![None; reports.len()];
+    let mut queue = Vec::new();
+    if let Some(first) = reports.first() {
+        aligned[0] = Some((first.clone(), [0; 3]));
+        queue.push(0);
+    }
+    while let Some(i) = queue.pop() {
+        let known = aligned[i].as_ref().unwrap().0.clone();
+        for j in 0..reports.len() {
+            if aligned[j].is_some()
+                || shared_distances(&fingerprints[i], &fingerprints[j]) < min_shared
+            {
+                continue;
+            }
+            if let Some(found) = try_align(&known, &reports[j], &rotations) {
+                aligned[j] = Some(found);
+                queue.push(j);
+            }
+        }
+    }
+    if let Some(lost) = aligned.iter().position(Option::is_none) {
+        return Err(lost);
+    }
+    let (beacons, scanners): (Vec<_>, Vec<_>) = aligned.into_iter().flatten().unzip();
+    Ok(Map {
+        scanners,
+        beacons: beacons.into_iter().flatten().collect(),
+    })
+}
+
+/// A scanner's report, along with its `--- scanner N ---` header line and that line's index.
+struct Report<'a> {
+    header: (usize, &'a str),
+    beacons: Vec<Point>,
+}
+
+fn parse_reports(input: &str) -> Result<Vec<Report<'_>>> {
+    let mut reports: Vec<Report> = Vec::new();
+    for (i, line) in input.lines().enumerate() {
+        if line.is_empty() {
+            continue;
+        }
+        if line.starts_with("---") {
+            reports.push(Report {
+                header: (i, line),
+                beacons: Vec::new(),
+            });
+            continue;
+        }
+        let report = reports
+            .last_mut()
+            .ok_or_else(|| Error::line(i, line, "expected a `--- scanner N ---` header"))?;
+        let coordinates: Vec<&str> = line.split(',').collect();
+        let point: [&str; 3] = coordinates
+            .try_into()
+            .map_err(|_| Error::line(i, line, "expected a beacon `x,y,z`"))?;
+        let mut beacon = [0; 3];
+        for (b, token) in beacon.iter_mut().zip(point) {
+            *b = token.parse().map_err(|e| Error::parse(i, line, token, e))?;
+        }
+        report.beacons.push(beacon);
+    }
+    Ok(reports)
+}
+
+/// Parses the scanner reports and aligns them into a single map.
+///
+/// Alignment is the bulk of the work, but it happens here rather than in the parts so that it
+/// runs once, and so that a scanner that can't be aligned is reported at its header line.
+pub fn parse(input: &str) -> Result<Map> {
+    let reports = parse_reports(input)?;
+    let beacons: Vec<Vec<Point>> = reports.iter().map(|r| r.beacons.clone()).collect();
+    align(&beacons).map_err(|lost| {
+        let (i, line) = reports[lost].header;
+        Error::line(
+            i,
+            line,
+            format!(
+                "scanner shares fewer than {} beacons with the others",
+                MIN_OVERLAP
+            ),
+        )
+    })
+}
+
+pub fn part_one(map: &Map) -> usize {
+    map.beacons.len()
+}
+
+/// The largest Manhattan distance between any two scanners.
+pub fn part_two(map: &Map) -> i64 {
+    map.scanners
+        .iter()
+        .flat_map(|&a| map.scanners.iter().map(move |&b| manhattan(a, b)))
+        .max()
+        .unwrap_or(0)
+}
+
+pub struct Day19;
+
+impl Solver for Day19 {
+    const DAY: u8 = 19;
+    const NAME: &'static str = "Beacon Scanner";
+    type Input = Map;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part_one(map: &Self::Input) -> Answer {
+        part_one(map).into()
+    }
+
+    fn part_two(map: &Self::Input) -> Answer {
+        part_two(map).into()
+    }
+}
@@ -1,17 +1,20 @@
-use crate::utils::{input, parse_input};
+use crate::answer::Answer;
+use crate::error;
+use crate::solver::Solver;
+use crate::utils::parse_input;
 use std::error::Error;
 use std::fmt::{Display, Formatter};
 use std::str::FromStr;
 
-#[derive(Copy, Clone)]
-enum Direction {
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Direction {
     Forward,
     Down,
     Up,
 }
 
 #[derive(Debug)]
-struct ParseDirectionError;
+pub struct ParseDirectionError;
 
 impl Display for ParseDirectionError {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
@@ -34,14 +37,14 @@ impl FromStr for Direction {
     }
 }
 
-#[derive(Copy, Clone)]
-struct Move {
-    dir: Direction,
-    length: u64,
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Move {
+    pub dir: Direction,
+    pub length: u64,
 }
 
 #[derive(Debug)]
-struct ParseMoveError;
+pub struct ParseMoveError;
 
 impl Display for ParseMoveError {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
@@ -67,14 +70,14 @@ impl FromStr for Move {
 }
 
 impl Move {
-    fn dx(&self) -> i64 {
+    pub fn dx(&self) -> i64 {
         (match self.dir {
             Direction::Forward => self.length,
             _ => 0,
         }) as i64
     }
 
-    fn dy(&self) -> i64 {
+    pub fn dy(&self) -> i64 {
         match self.dir {
             Direction::Up => -(self.length as i64),
             Direction::Down => self.length as i64,
@@ -83,15 +86,17 @@ impl Move {
     }
 }
 
-struct Position {
-    x: i64,
-    y: i64,
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Position {
+    pub x: i64,
+    pub y: i64,
 }
 
-struct AimPosition {
-    x: i64,
-    y: i64,
-    aim: i64,
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct AimPosition {
+    pub x: i64,
+    pub y: i64,
+    pub aim: i64,
 }
 
 impl AimPosition {
@@ -106,13 +111,13 @@ impl From<AimPosition> for Position {
     }
 }
 
-fn make_moves_no_aim(moves: &[Move]) -> Position {
+pub fn make_moves_no_aim(moves: &[Move]) -> Position {
     let x = moves.iter().map(Move::dx).sum();
     let y = moves.iter().map(Move::dy).sum();
     Position { x, y }
 }
 
-fn make_moves_aim(moves: &[Move]) -> AimPosition {
+pub fn make_moves_aim(moves: &[Move]) -> AimPosition {
     moves
         .iter()
         .fold(AimPosition::new(), |acc, mv| match mv.dir {
@@ -129,9 +134,36 @@ fn make_moves_aim(moves: &[Move]) -> AimPosition {
         })
 }
 
-pub(crate) fn solve() -> (i64, i64) {
-    let v = parse_input(&input());
-    let pos = make_moves_no_aim(&v);
-    let aim_pos = Position::from(make_moves_aim(&v));
-    (pos.x * pos.y, aim_pos.x * aim_pos.y)
+pub fn parse(input: &str) -> error::Result<Vec<Move>> {
+    parse_input(input)
+}
+
+pub fn part_one(moves: &[Move]) -> i64 {
+    let pos = make_moves_no_aim(moves);
+    pos.x * pos.y
+}
+
+pub fn part_two(moves: &[Move]) -> i64 {
+    let pos = Position::from(make_moves_aim(moves));
+    pos.x * pos.y
+}
+
+pub struct Day2;
+
+impl Solver for Day2 {
+    const DAY: u8 = 2;
+    const NAME: &'static str = "Dive!";
+    type Input = Vec<Move>;
+
+    fn parse(input: &str) -> error::Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part_one(moves: &Self::Input) -> Answer {
+        part_one(moves).into()
+    }
+
+    fn part_two(moves: &Self::Input) -> Answer {
+        part_two(moves).into()
+    }
 }
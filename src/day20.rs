@@ -0,0 +1,137 @@
+use crate::answer::Answer;
+use crate::error::{Error, Result};
+use crate::solver::Solver;
+use crate::utils::{block_3x3, parse_grid_lines, Grid};
+
+/// The number of entries in the enhancement algorithm, one per possible 3x3 block of pixels.
+const ALGORITHM_LEN: usize = 512;
+
+/// An infinite image: a finite grid of pixels surrounded by a uniform background.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Image {
+    pub pixels: Grid<bool>,
+    /// Whether every pixel outside `pixels` is lit.
+    pub background: bool,
+}
+
+impl Image {
+    /// Whether the pixel at `(r, c)` is lit, where positions outside `pixels` are background.
+    fn lit(&self, r: isize, c: isize) -> bool {
+        if r < 0 || c < 0 {
+            return self.background;
+        }
+        let pixel = self.pixels.get((r as usize, c as usize));
+        pixel.copied().unwrap_or(self.background)
+    }
+
+    /// Applies the enhancement algorithm once, growing the image by one pixel on every side.
+    pub fn enhance(&self, algorithm: &[bool; ALGORITHM_LEN]) -> Image {
+        let (width, height) = (self.pixels.width() + 2, self.pixels.height() + 2);
+        let pixels = (0..height as isize)
+            .flat_map(|r| (0..width as isize).map(move |c| (r, c)))
+            .map(|(r, c)| {
+                // Position (r, c) in the enhanced image is (r - 1, c - 1) in this one.
+                let index = block_3x3().fold(0, |acc, (dr, dc)| {
+                    acc << 1 | self.lit(r - 1 + dr, c - 1 + dc) as usize
+                });
+                algorithm[index]
+            })
+            .collect();
+        let background_index = if self.background {
+            ALGORITHM_LEN - 1
+        } else {
+            0
+        };
+        Image {
+            pixels: Grid::new(width, height, pixels),
+            background: algorithm[background_index],
+        }
+    }
+
+    /// The number of lit pixels, or `None` if the background is lit and so there are infinitely
+    /// many.
+    pub fn count_lit(&self) -> Option<usize> {
+        if self.background {
+            return None;
+        }
+        let lit = self.pixels.positions().filter(|&p| self.pixels[p]);
+        Some(lit.count())
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct TrenchMap {
+    pub algorithm: [bool; ALGORITHM_LEN],
+    pub image: Image,
+}
+
+impl TrenchMap {
+    /// Counts the lit pixels after enhancing the image `times` times.
+    pub fn count_lit_after(&self, times: usize) -> Option<usize> {
+        let image = (0..times).fold(self.image.clone(), |image, _| {
+            image.enhance(&self.algorithm)
+        });
+        image.count_lit()
+    }
+}
+
+fn parse_pixels(line_index: usize, line: &str) -> Result<Vec<bool>> {
+    line.char_indices()
+        .map(|(i, ch)| match ch {
+            '#' => Ok(true),
+            '.' => Ok(false),
+            _ => {
+                let token = &line[i..i + ch.len_utf8()];
+                Err(Error::parse(line_index, line, token, "expected `#` or `.`"))
+            }
+        })
+        .collect()
+}
+
+pub fn parse(input: &str) -> Result<TrenchMap> {
+    let mut lines = input.lines().enumerate();
+    let (_, first) = lines.next().unwrap_or((0, ""));
+    let algorithm = parse_pixels(0, first)?.try_into().map_err(|_| {
+        Error::line(
+            0,
+            first,
+            format!("expected {} algorithm entries", ALGORITHM_LEN),
+        )
+    })?;
+    let pixels = parse_grid_lines(lines.skip_while(|(_, line)| line.is_empty()), parse_pixels)?;
+    Ok(TrenchMap {
+        algorithm,
+        image: Image {
+            pixels,
+            background: false,
+        },
+    })
+}
+
+pub fn part_one(map: &TrenchMap) -> Option<usize> {
+    map.count_lit_after(2)
+}
+
+pub fn part_two(map: &TrenchMap) -> Option<usize> {
+    map.count_lit_after(50)
+}
+
+pub struct Day20;
+
+impl Solver for Day20 {
+    const DAY: u8 = 20;
+    const NAME: &'static str = "Trench Map";
+    type Input = TrenchMap;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part_one(map: &Self::Input) -> Answer {
+        Answer::or_text(part_one(map), "infinitely many")
+    }
+
+    fn part_two(map: &Self::Input) -> Answer {
+        Answer::or_text(part_two(map), "infinitely many")
+    }
+}
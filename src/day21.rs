@@ -0,0 +1,116 @@
+use crate::answer::Answer;
+use crate::error::{Error, Result};
+use crate::solver::Solver;
+use std::collections::HashMap;
+
+const TRACK_LEN: u32 = 10;
+
+/// How many of the 27 universes split off by three rolls of the quantum die give each total.
+const ROLL_WEIGHTS: [(u32, u64); 7] = [(3, 1), (4, 3), (5, 6), (6, 7), (7, 6), (8, 3), (9, 1)];
+
+/// Each player's starting space, from 1 to 10.
+pub type Start = [u32; 2];
+
+fn advance(position: u32, roll: u32) -> u32 {
+    (position + roll - 1) % TRACK_LEN + 1
+}
+
+/// Plays with the deterministic 100-sided die to 1000 points, returning the losing score times
+/// the number of rolls.
+pub fn play_deterministic(start: Start) -> u64 {
+    let (mut positions, mut scores) = (start, [0; 2]);
+    let mut die = (1..=100).cycle();
+    let mut rolls = 0;
+    for player in [0, 1].into_iter().cycle() {
+        let roll: u32 = die.by_ref().take(3).sum();
+        rolls += 3;
+        positions[player] = advance(positions[player], roll);
+        scores[player] += positions[player];
+        if scores[player] >= 1000 {
+            return scores[1 - player] as u64 * rolls;
+        }
+    }
+    unreachable!("Someone always reaches 1000 points")
+}
+
+type Universe = ([u32; 2], [u32; 2], usize);
+
+/// Counts the universes in which each player wins with the quantum die, first to 21 points.
+pub fn count_wins(start: Start) -> [u64; 2] {
+    let mut memo = HashMap::new();
+    count_wins_from((start, [0; 2], 0), &mut memo)
+}
+
+fn count_wins_from(universe: Universe, memo: &mut HashMap<Universe, [u64; 2]>) -> [u64; 2] {
+    if let Some(&wins) = memo.get(&universe) {
+        return wins;
+    }
+    let (positions, scores, turn) = universe;
+    let mut wins = [0; 2];
+    for (roll, weight) in ROLL_WEIGHTS {
+        let (mut positions, mut scores) = (positions, scores);
+        positions[turn] = advance(positions[turn], roll);
+        scores[turn] += positions[turn];
+        if scores[turn] >= 21 {
+            wins[turn] += weight;
+        } else {
+            let later = count_wins_from((positions, scores, 1 - turn), memo);
+            wins[0] += weight * later[0];
+            wins[1] += weight * later[1];
+        }
+    }
+    memo.insert(universe, wins);
+    wins
+}
+
+pub fn parse(input: &str) -> Result<Start> {
+    let mut start = [0; 2];
+    let mut lines = input.lines().enumerate();
+    for (player, space) in start.iter_mut().enumerate() {
+        let (i, line) = lines.next().unwrap_or((player, ""));
+        let prefix = format!("Player {} starting position: ", player + 1);
+        let token = line
+            .strip_prefix(&prefix)
+            .ok_or_else(|| Error::line(i, line, format!("expected `{}N`", prefix)))?;
+        *space = token.parse().map_err(|e| Error::parse(i, line, token, e))?;
+        if !(1..=TRACK_LEN).contains(space) {
+            return Err(Error::parse(
+                i,
+                line,
+                token,
+                "expected a space from 1 to 10",
+            ));
+        }
+    }
+    Ok(start)
+}
+
+pub fn part_one(start: &Start) -> u64 {
+    play_deterministic(*start)
+}
+
+/// The number of universes in which the player who wins more often wins.
+pub fn part_two(start: &Start) -> u64 {
+    let [a, b] = count_wins(*start);
+    a.max(b)
+}
+
+pub struct Day21;
+
+impl Solver for Day21 {
+    const DAY: u8 = 21;
+    const NAME: &'static str = "Dirac Dice";
+    type Input = Start;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part_one(start: &Self::Input) -> Answer {
+        part_one(start).into()
+    }
+
+    fn part_two(start: &Self::Input) -> Answer {
+        part_two(start).into()
+    }
+}
@@ -0,0 +1,140 @@
+use crate::answer::Answer;
+use crate::error::{Error, Result};
+use crate::solver::Solver;
+
+/// The half-width of the initialization region, centred on the origin.
+const INIT_REGION: i64 = 50;
+
+/// A cuboid of cubes, with inclusive bounds on each axis.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Cuboid {
+    pub min: [i64; 3],
+    pub max: [i64; 3],
+}
+
+impl Cuboid {
+    pub fn volume(&self) -> i64 {
+        (0..3).map(|i| self.max[i] - self.min[i] + 1).product()
+    }
+
+    pub fn intersection(&self, other: &Cuboid) -> Option<Cuboid> {
+        let min = [0, 1, 2].map(|i| self.min[i].max(other.min[i]));
+        let max = [0, 1, 2].map(|i| self.max[i].min(other.max[i]));
+        (0..3)
+            .all(|i| min[i] <= max[i])
+            .then_some(Cuboid { min, max })
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Step {
+    pub on: bool,
+    pub cuboid: Cuboid,
+}
+
+/// Counts the cubes left on after `steps`, considering only those within `region` if given.
+///
+/// Keeps a list of cuboids whose signed volumes add up to the lit cubes: each step cancels out
+/// its overlap with every cuboid so far, then adds itself back if it turns cubes on.
+pub fn count_lit(steps: &[Step], region: Option<&Cuboid>) -> i64 {
+    let mut signed: Vec<(Cuboid, i64)> = Vec::new();
+    for step in steps {
+        let cuboid = match region {
+            Some(region) => match step.cuboid.intersection(region) {
+                Some(clipped) => clipped,
+                None => continue,
+            },
+            None => step.cuboid,
+        };
+        let overlaps: Vec<(Cuboid, i64)> = signed
+            .iter()
+            .filter_map(|(other, sign)| cuboid.intersection(other).map(|i| (i, -sign)))
+            .collect();
+        signed.extend(overlaps);
+        if step.on {
+            signed.push((cuboid, 1));
+        }
+    }
+    signed.iter().map(|(c, sign)| sign * c.volume()).sum()
+}
+
+fn parse_step(line_index: usize, line: &str) -> Result<Step> {
+    let (state, ranges) = line
+        .split_once(' ')
+        .ok_or_else(|| Error::line(line_index, line, "expected `on|off x=..,y=..,z=..`"))?;
+    let on = match state {
+        "on" => true,
+        "off" => false,
+        _ => return Err(Error::parse(line_index, line, state, "expected on or off")),
+    };
+    let ranges: Vec<&str> = ranges.split(',').collect();
+    if ranges.len() != 3 {
+        return Err(Error::line(
+            line_index,
+            line,
+            "expected ranges for x, y and z",
+        ));
+    }
+    let mut cuboid = Cuboid {
+        min: [0; 3],
+        max: [0; 3],
+    };
+    for (axis, (&range, name)) in ranges.iter().zip(["x", "y", "z"]).enumerate() {
+        let bounds = range
+            .strip_prefix(name)
+            .and_then(|r| r.strip_prefix('='))
+            .and_then(|r| r.split_once(".."))
+            .ok_or_else(|| {
+                let reason = format!("expected `{}=min..max`", name);
+                Error::parse(line_index, line, range, reason)
+            })?;
+        let bound = |t: &str| {
+            t.parse::<i64>()
+                .map_err(|e| Error::parse(line_index, line, t, e))
+        };
+        let (a, b) = (bound(bounds.0)?, bound(bounds.1)?);
+        cuboid.min[axis] = a.min(b);
+        cuboid.max[axis] = a.max(b);
+    }
+    Ok(Step { on, cuboid })
+}
+
+pub fn parse(input: &str) -> Result<Vec<Step>> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| parse_step(i, line))
+        .collect()
+}
+
+pub fn part_one(steps: &[Step]) -> i64 {
+    let region = Cuboid {
+        min: [-INIT_REGION; 3],
+        max: [INIT_REGION; 3],
+    };
+    count_lit(steps, Some(&region))
+}
+
+pub fn part_two(steps: &[Step]) -> i64 {
+    count_lit(steps, None)
+}
+
+pub struct Day22;
+
+impl Solver for Day22 {
+    const DAY: u8 = 22;
+    const NAME: &'static str = "Reactor Reboot";
+    type Input = Vec<Step>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part_one(steps: &Self::Input) -> Answer {
+        part_one(steps).into()
+    }
+
+    fn part_two(steps: &Self::Input) -> Answer {
+        part_two(steps).into()
+    }
+}
@@ -0,0 +1,271 @@
+use crate::answer::Answer;
+use crate::error::{Error, Result};
+use crate::solver::Solver;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::fmt::{Display, Formatter};
+
+const HALLWAY_LEN: usize = 11;
+const ROOMS: usize = 4;
+const MAX_DEPTH: usize = 4;
+/// Bits per cell: 0 for empty, or 1 to 4 for amphipods `A` to `D`.
+const CELL_BITS: usize = 3;
+
+/// The hallway spaces amphipods may stop in, which excludes those right outside a room.
+const STOPS: [usize; 7] = [0, 1, 3, 5, 7, 9, 10];
+
+/// The rows folded out of the diagram for part two, inserted below each room's top amphipod.
+const UNFOLDED_ROWS: [[u8; ROOMS]; 2] = [[4, 3, 2, 1], [4, 2, 1, 3]];
+
+/// The hallway space right outside `room`.
+fn door(room: usize) -> usize {
+    2 + 2 * room
+}
+
+fn energy(amphipod: u8) -> u64 {
+    10u64.pow(amphipod as u32 - 1)
+}
+
+/// The burrow, with every space packed into one integer.
+///
+/// Spaces `0..11` are the hallway from left to right, followed by each room's spaces from the top
+/// down, with `MAX_DEPTH` spaces set aside per room whatever its actual depth.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Burrow {
+    cells: u128,
+    depth: usize,
+}
+
+impl Burrow {
+    fn get(&self, space: usize) -> u8 {
+        (self.cells >> (CELL_BITS * space) & 0b111) as u8
+    }
+
+    fn set(&mut self, space: usize, amphipod: u8) {
+        let shift = CELL_BITS * space;
+        self.cells = self.cells & !(0b111 << shift) | (amphipod as u128) << shift;
+    }
+
+    fn room_space(room: usize, slot: usize) -> usize {
+        HALLWAY_LEN + MAX_DEPTH * room + slot
+    }
+
+    fn room(&self, room: usize) -> impl Iterator<Item = u8> + '_ {
+        (0..self.depth).map(move |slot| self.get(Self::room_space(room, slot)))
+    }
+
+    /// Whether a room holds only the amphipods that belong in it, so they can stay put and more
+    /// can join them.
+    fn is_settled(&self, room: usize) -> bool {
+        self.room(room).all(|a| a == 0 || a as usize == room + 1)
+    }
+
+    pub fn is_organised(&self) -> bool {
+        (0..ROOMS).all(|room| self.room(room).all(|a| a as usize == room + 1))
+    }
+
+    /// Whether the hallway spaces strictly between `from` and `to`, plus `to` itself, are empty.
+    fn hallway_clear(&self, from: usize, to: usize) -> bool {
+        let (lo, hi) = if from < to {
+            (from + 1, to)
+        } else {
+            (to, from - 1)
+        };
+        (lo..=hi).all(|space| self.get(space) == 0)
+    }
+
+    /// Every burrow reachable by moving one amphipod, with the energy that takes.
+    fn moves(&self) -> Vec<(Burrow, u64)> {
+        let mut moves = Vec::new();
+        // An amphipod in the hallway can only move into its own room, all the way down.
+        for from in 0..HALLWAY_LEN {
+            let amphipod = self.get(from);
+            if amphipod == 0 {
+                continue;
+            }
+            let room = amphipod as usize - 1;
+            if !self.is_settled(room) || !self.hallway_clear(from, door(room)) {
+                continue;
+            }
+            let slot = self.room(room).take_while(|&a| a == 0).count() - 1;
+            let mut next = *self;
+            next.set(from, 0);
+            next.set(Self::room_space(room, slot), amphipod);
+            let steps = from.abs_diff(door(room)) + slot + 1;
+            moves.push((next, steps as u64 * energy(amphipod)));
+        }
+        // The top amphipod of a room that isn't settled moves out to somewhere in the hallway.
+        for room in (0..ROOMS).filter(|&room| !self.is_settled(room)) {
+            let slot = self.room(room).take_while(|&a| a == 0).count();
+            let amphipod = self.get(Self::room_space(room, slot));
+            for to in STOPS {
+                if !self.hallway_clear(door(room), to) {
+                    continue;
+                }
+                let mut next = *self;
+                next.set(Self::room_space(room, slot), 0);
+                next.set(to, amphipod);
+                let steps = slot + 1 + door(room).abs_diff(to);
+                moves.push((next, steps as u64 * energy(amphipod)));
+            }
+        }
+        moves
+    }
+
+    /// Folds out the two extra rows of amphipods hidden in a 2-deep diagram.
+    pub fn unfold(&self) -> Burrow {
+        assert_eq!(self.depth, 2, "Only 2-deep burrows can be unfolded");
+        let mut unfolded = Burrow {
+            cells: self.cells,
+            depth: MAX_DEPTH,
+        };
+        for room in 0..ROOMS {
+            let bottom = self.get(Self::room_space(room, 1));
+            for (slot, row) in UNFOLDED_ROWS.iter().enumerate() {
+                unfolded.set(Self::room_space(room, slot + 1), row[room]);
+            }
+            unfolded.set(Self::room_space(room, 3), bottom);
+        }
+        unfolded
+    }
+
+    /// Finds the least energy needed to organise the amphipods, using Dijkstra's algorithm, along
+    /// with every burrow on the way from this one to the organised one.
+    pub fn organise(&self) -> Option<(u64, Vec<Burrow>)> {
+        let mut best: HashMap<Burrow, u64> = HashMap::from([(*self, 0)]);
+        let mut previous: HashMap<Burrow, Burrow> = HashMap::new();
+        let mut queue = BinaryHeap::from([Reverse((0, self.cells))]);
+        while let Some(Reverse((cost, cells))) = queue.pop() {
+            let burrow = Burrow {
+                cells,
+                depth: self.depth,
+            };
+            if burrow.is_organised() {
+                let mut path = vec![burrow];
+                while let Some(&before) = previous.get(path.last().unwrap()) {
+                    path.push(before);
+                }
+                path.reverse();
+                return Some((cost, path));
+            }
+            if cost > best[&burrow] {
+                continue;
+            }
+            for (next, energy) in burrow.moves() {
+                let next_cost = cost + energy;
+                if best.get(&next).is_none_or(|&b| next_cost < b) {
+                    best.insert(next, next_cost);
+                    previous.insert(next, burrow);
+                    queue.push(Reverse((next_cost, next.cells)));
+                }
+            }
+        }
+        None
+    }
+}
+
+fn amphipod_char(amphipod: u8) -> char {
+    match amphipod {
+        0 => '.',
+        a => (b'A' + a - 1) as char,
+    }
+}
+
+impl Display for Burrow {
+    /// Draws the burrow the same way as the puzzle input.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "#############")?;
+        let hallway: String = (0..HALLWAY_LEN)
+            .map(|s| amphipod_char(self.get(s)))
+            .collect();
+        writeln!(f, "#{}#", hallway)?;
+        for slot in 0..self.depth {
+            let (edge, end) = if slot == 0 {
+                ("###", "###")
+            } else {
+                ("  #", "#")
+            };
+            let row: Vec<String> = (0..ROOMS)
+                .map(|room| amphipod_char(self.get(Self::room_space(room, slot))).to_string())
+                .collect();
+            writeln!(f, "{}{}{}", edge, row.join("#"), end)?;
+        }
+        write!(f, "  #########")
+    }
+}
+
+/// Parses the diagram of a burrow with an empty hallway and full rooms, of any depth up to 4.
+pub fn parse(input: &str) -> Result<Burrow> {
+    let lines: Vec<&str> = input.lines().collect();
+    let hallway = lines.get(1).copied().unwrap_or_default();
+    if hallway.trim() != format!("#{}#", ".".repeat(HALLWAY_LEN)) {
+        return Err(Error::line(1, hallway, "expected an empty hallway"));
+    }
+    let mut burrow = Burrow { cells: 0, depth: 0 };
+    let rows = lines.iter().enumerate().skip(2);
+    for (i, line) in rows.take_while(|(_, line)| line.trim() != "#########") {
+        if burrow.depth == MAX_DEPTH {
+            return Err(Error::line(i, line, "rooms can be at most 4 deep"));
+        }
+        for room in 0..ROOMS {
+            let column = 3 + 2 * room;
+            let token = line.get(column..column + 1).unwrap_or(line);
+            let amphipod = match token {
+                "A" | "B" | "C" | "D" => token.as_bytes()[0] - b'A' + 1,
+                _ => return Err(Error::parse(i, line, token, "expected an amphipod A-D")),
+            };
+            burrow.set(Burrow::room_space(room, burrow.depth), amphipod);
+        }
+        burrow.depth += 1;
+    }
+    // Each kind of amphipod must have exactly enough of its kind to fill its room.
+    let count = |a: u8| {
+        (0..ROOMS)
+            .flat_map(|r| burrow.room(r))
+            .filter(|&b| b == a)
+            .count()
+    };
+    if burrow.depth == 0 || (1..=ROOMS as u8).any(|a| count(a) != burrow.depth) {
+        let line = lines.first().copied().unwrap_or_default();
+        return Err(Error::line(
+            0,
+            line,
+            "expected enough of each kind of amphipod to fill one room",
+        ));
+    }
+    Ok(burrow)
+}
+
+pub fn part_one(burrow: &Burrow) -> Option<u64> {
+    burrow.organise().map(|(energy, _)| energy)
+}
+
+/// Organises the unfolded burrow, or the burrow as it is if it's already 4 deep.
+pub fn part_two(burrow: &Burrow) -> Option<u64> {
+    let burrow = if burrow.depth == 2 {
+        burrow.unfold()
+    } else {
+        *burrow
+    };
+    burrow.organise().map(|(energy, _)| energy)
+}
+
+pub struct Day23;
+
+impl Solver for Day23 {
+    const DAY: u8 = 23;
+    const NAME: &'static str = "Amphipod";
+    type Input = Burrow;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part_one(burrow: &Self::Input) -> Answer {
+        Answer::or_text(part_one(burrow), "impossible")
+    }
+
+    fn part_two(burrow: &Self::Input) -> Answer {
+        Answer::or_text(part_two(burrow), "impossible")
+    }
+}
@@ -0,0 +1,404 @@
+use crate::answer::Answer;
+use crate::error::{Error, Result};
+use crate::solver::Solver;
+use std::collections::HashSet;
+
+/// The register the model number checker leaves `0` in to accept a model number.
+const Z: usize = 3;
+
+/// Registers `w`, `x`, `y` and `z`, by index.
+pub type Registers = [i64; 4];
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Operand {
+    Register(usize),
+    Literal(i64),
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Instruction {
+    Inp(usize),
+    Add(usize, Operand),
+    Mul(usize, Operand),
+    Div(usize, Operand),
+    Mod(usize, Operand),
+    Eql(usize, Operand),
+}
+
+/// Runs `program` from `registers`, reading from `inputs` for each `inp`.
+///
+/// Returns `None` if the program divides by zero, takes the remainder of a negative number or by
+/// a non-positive one, or runs out of input.
+pub fn run(
+    program: &[Instruction],
+    mut registers: Registers,
+    inputs: impl IntoIterator<Item = i64>,
+) -> Option<Registers> {
+    let mut inputs = inputs.into_iter();
+    for &instruction in program {
+        let value = |operand| match operand {
+            Operand::Register(r) => registers[r],
+            Operand::Literal(n) => n,
+        };
+        let (a, result) = match instruction {
+            Instruction::Inp(a) => (a, inputs.next()?),
+            Instruction::Add(a, b) => (a, registers[a].checked_add(value(b))?),
+            Instruction::Mul(a, b) => (a, registers[a].checked_mul(value(b))?),
+            Instruction::Div(a, b) => (a, registers[a].checked_div(value(b))?),
+            Instruction::Mod(a, b) if registers[a] >= 0 && value(b) > 0 => {
+                (a, registers[a] % value(b))
+            }
+            Instruction::Mod(..) => return None,
+            Instruction::Eql(a, b) => (a, (registers[a] == value(b)) as i64),
+        };
+        registers[a] = result;
+    }
+    Some(registers)
+}
+
+/// Whether `digits` is a model number `program` accepts.
+pub fn accepts(program: &[Instruction], digits: &[i64]) -> bool {
+    run(program, [0; 4], digits.iter().copied()).is_some_and(|r| r[Z] == 0)
+}
+
+/// Which accepted model number to look for.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Goal {
+    Largest,
+    Smallest,
+}
+
+impl Goal {
+    fn digits(&self) -> [i64; 9] {
+        match self {
+            Goal::Largest => [9, 8, 7, 6, 5, 4, 3, 2, 1],
+            Goal::Smallest => [1, 2, 3, 4, 5, 6, 7, 8, 9],
+        }
+    }
+}
+
+/// The constants that vary between the 14 otherwise identical blocks of the puzzle's program.
+#[derive(Copy, Clone, Debug)]
+struct BlockConstants {
+    /// Whether the block divides `z` by 26, popping a base-26 digit off it.
+    pops: bool,
+    /// Added to the popped digit, which the input digit must equal to avoid pushing.
+    check: i64,
+    /// Added to the input digit before it's pushed.
+    offset: i64,
+}
+
+/// The block the puzzle's program repeats for each digit, given its constants.
+fn block_template(div: i64, check: i64, offset: i64) -> [Instruction; 18] {
+    use Instruction::*;
+    use Operand::{Literal, Register};
+    let (w, x, y, z) = (0, 1, 2, Z);
+    [
+        Inp(w),
+        Mul(x, Literal(0)),
+        Add(x, Register(z)),
+        Mod(x, Literal(26)),
+        Div(z, Literal(div)),
+        Add(x, Literal(check)),
+        Eql(x, Register(w)),
+        Eql(x, Literal(0)),
+        Mul(y, Literal(0)),
+        Add(y, Literal(25)),
+        Mul(y, Register(x)),
+        Add(y, Literal(1)),
+        Mul(z, Register(y)),
+        Mul(y, Literal(0)),
+        Add(y, Register(w)),
+        Add(y, Literal(offset)),
+        Mul(y, Register(x)),
+        Add(z, Register(y)),
+    ]
+}
+
+/// Matches `block` against the shape every block of the puzzle's program takes.
+fn block_constants(block: &[Instruction]) -> Option<BlockConstants> {
+    let literal = |index| match block.get(index)? {
+        Instruction::Div(_, Operand::Literal(n)) | Instruction::Add(_, Operand::Literal(n)) => {
+            Some(*n)
+        }
+        _ => None,
+    };
+    let (div, check, offset) = (literal(4)?, literal(5)?, literal(15)?);
+    if block != block_template(div, check, offset) {
+        return None;
+    }
+    let pops = match div {
+        1 => false,
+        26 => true,
+        _ => return None,
+    };
+    Some(BlockConstants {
+        pops,
+        check,
+        offset,
+    })
+}
+
+/// Solves the puzzle's program by pairing up the blocks that push and pop each base-26 digit of
+/// `z`: each pair only leaves `z` unchanged if its second input equals its first plus a fixed
+/// difference, and `z` ends at zero only if every pair does.
+fn solve_by_structure(blocks: &[&[Instruction]], goal: Goal) -> Option<Vec<i64>> {
+    let constants: Vec<BlockConstants> = blocks
+        .iter()
+        .map(|b| block_constants(b))
+        .collect::<Option<_>>()?;
+    let mut digits = vec![0; blocks.len()];
+    let mut stack = Vec::new();
+    for (i, block) in constants.iter().enumerate() {
+        if !block.pops {
+            // A block that doesn't pop must push whatever the digit, which it only does if no
+            // base-26 digit plus `check` can equal an input digit.
+            if (-24..=9).contains(&block.check) {
+                return None;
+            }
+            stack.push(i);
+            continue;
+        }
+        let j = stack.pop()?;
+        let difference = constants[j].offset + block.check;
+        let first = match goal {
+            Goal::Largest => 9.min(9 - difference),
+            Goal::Smallest => 1.max(1 - difference),
+        };
+        let second = first + difference;
+        if !(1..=9).contains(&first) || !(1..=9).contains(&second) {
+            return None;
+        }
+        digits[j] = first;
+        digits[i] = second;
+    }
+    stack.is_empty().then_some(digits)
+}
+
+/// Solves any program by trying digits block by block, remembering the states after which no
+/// choice of the remaining digits is accepted. This is much slower than [`solve_by_structure`].
+fn solve_by_search(blocks: &[&[Instruction]], start: Registers, goal: Goal) -> Option<Vec<i64>> {
+    fn search(
+        blocks: &[&[Instruction]],
+        registers: Registers,
+        goal: Goal,
+        dead_ends: &mut HashSet<(usize, Registers)>,
+        digits: &mut Vec<i64>,
+    ) -> bool {
+        let Some((block, rest)) = blocks.split_first() else {
+            return registers[Z] == 0;
+        };
+        if dead_ends.contains(&(rest.len(), registers)) {
+            return false;
+        }
+        for digit in goal.digits() {
+            let Some(next) = run(block, registers, [digit]) else {
+                continue;
+            };
+            digits.push(digit);
+            if search(rest, next, goal, dead_ends, digits) {
+                return true;
+            }
+            digits.pop();
+        }
+        dead_ends.insert((rest.len(), registers));
+        false
+    }
+    let mut digits = Vec::new();
+    search(blocks, start, goal, &mut HashSet::new(), &mut digits).then_some(digits)
+}
+
+/// Finds the largest or smallest model number the program accepts.
+pub fn find_model_number(program: &[Instruction], goal: Goal) -> Option<u64> {
+    // Split the program into blocks that each start by reading a digit. Any instructions before
+    // the first `inp` can't depend on the input, so they're run up front.
+    let first_input = program
+        .iter()
+        .position(|i| matches!(i, Instruction::Inp(_)))?;
+    let (prelude, body) = program.split_at(first_input);
+    let mut blocks: Vec<&[Instruction]> = Vec::new();
+    let mut rest = body;
+    while !rest.is_empty() {
+        let len = rest[1..]
+            .iter()
+            .position(|i| matches!(i, Instruction::Inp(_)))
+            .map_or(rest.len(), |p| p + 1);
+        let (block, tail) = rest.split_at(len);
+        blocks.push(block);
+        rest = tail;
+    }
+    let structured = prelude
+        .is_empty()
+        .then(|| solve_by_structure(&blocks, goal))
+        .flatten()
+        .filter(|digits| accepts(program, digits));
+    let digits = match structured {
+        Some(digits) => digits,
+        None => solve_by_search(&blocks, run(prelude, [0; 4], [])?, goal)?,
+    };
+    Some(digits.iter().fold(0, |acc, &d| 10 * acc + d as u64))
+}
+
+fn parse_register(line_index: usize, line: &str, token: &str) -> Result<usize> {
+    match token {
+        "w" => Ok(0),
+        "x" => Ok(1),
+        "y" => Ok(2),
+        "z" => Ok(3),
+        _ => Err(Error::parse(
+            line_index,
+            line,
+            token,
+            "expected a register w, x, y or z",
+        )),
+    }
+}
+
+fn parse_instruction(line_index: usize, line: &str) -> Result<Instruction> {
+    let tokens: Vec<&str> = line.split_whitespace().collect();
+    let operand = |token: &str| match token.parse() {
+        Ok(n) => Ok(Operand::Literal(n)),
+        Err(_) => parse_register(line_index, line, token).map(Operand::Register),
+    };
+    match tokens[..] {
+        ["inp", a] => Ok(Instruction::Inp(parse_register(line_index, line, a)?)),
+        [op, a, b] => {
+            let make = match op {
+                "add" => Instruction::Add,
+                "mul" => Instruction::Mul,
+                "div" => Instruction::Div,
+                "mod" => Instruction::Mod,
+                "eql" => Instruction::Eql,
+                _ => return Err(Error::parse(line_index, line, op, "unknown instruction")),
+            };
+            Ok(make(parse_register(line_index, line, a)?, operand(b)?))
+        }
+        _ => Err(Error::line(
+            line_index,
+            line,
+            "expected `inp a` or `op a b`",
+        )),
+    }
+}
+
+pub fn parse(input: &str) -> Result<Vec<Instruction>> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| parse_instruction(i, line))
+        .collect()
+}
+
+pub fn part_one(program: &[Instruction]) -> Option<u64> {
+    find_model_number(program, Goal::Largest)
+}
+
+pub fn part_two(program: &[Instruction]) -> Option<u64> {
+    find_model_number(program, Goal::Smallest)
+}
+
+pub struct Day24;
+
+impl Solver for Day24 {
+    const DAY: u8 = 24;
+    const NAME: &'static str = "Arithmetic Logic Unit";
+    type Input = Vec<Instruction>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part_one(program: &Self::Input) -> Answer {
+        Answer::or_text(part_one(program), "none accepted")
+    }
+
+    fn part_two(program: &Self::Input) -> Answer {
+        Answer::or_text(part_two(program), "none accepted")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `(div, check, offset)` for each block, pushing 7 digits and then popping them in reverse.
+    const BLOCKS: [(i64, i64, i64); 14] = [
+        (1, 11, 3),
+        (1, 12, 7),
+        (1, 10, 1),
+        (1, 14, 12),
+        (1, 13, 0),
+        (1, 15, 6),
+        (1, 10, 2),
+        (26, -2, 5),
+        (26, -14, 8),
+        (26, 8, 4),
+        (26, -15, 9),
+        (26, 4, 2),
+        (26, -7, 1),
+        (26, -5, 6),
+    ];
+
+    fn templated_program() -> Vec<Instruction> {
+        BLOCKS
+            .iter()
+            .flat_map(|&(div, check, offset)| block_template(div, check, offset))
+            .collect()
+    }
+
+    fn digits(n: u64) -> Vec<i64> {
+        n.to_string().bytes().map(|b| (b - b'0') as i64).collect()
+    }
+
+    #[test]
+    fn runs_instructions() {
+        let program = parse("inp x\nmul x -1\ninp y\nadd y 2\neql y x\nmod x 3\n").unwrap();
+        assert_eq!(run(&program, [0; 4], [-4, 2]), Some([0, 1, 1, 0]));
+        assert_eq!(run(&program, [0; 4], [-4, 7]), Some([0, 1, 0, 0]));
+        assert_eq!(run(&program, [0; 4], [-4]), None);
+    }
+
+    #[test]
+    fn rejects_invalid_arithmetic() {
+        let mod_zero = [Instruction::Mod(0, Operand::Literal(0))];
+        assert_eq!(run(&mod_zero, [5, 0, 0, 0], []), None);
+        let mod_negative = [Instruction::Mod(0, Operand::Literal(3))];
+        assert_eq!(run(&mod_negative, [-5, 0, 0, 0], []), None);
+        let mod_by_negative = [Instruction::Mod(0, Operand::Register(1))];
+        assert_eq!(run(&mod_by_negative, [5, -3, 0, 0], []), None);
+        let div_zero = [Instruction::Div(0, Operand::Literal(0))];
+        assert_eq!(run(&div_zero, [5, 0, 0, 0], []), None);
+    }
+
+    #[test]
+    fn matches_block_constants() {
+        let constants = block_constants(&block_template(26, -7, 1)).unwrap();
+        assert!(constants.pops);
+        assert_eq!((constants.check, constants.offset), (-7, 1));
+        let mut altered = block_template(26, -7, 1);
+        altered[9] = Instruction::Add(2, Operand::Literal(24));
+        assert!(block_constants(&altered).is_none());
+        assert!(block_constants(&block_template(2, -7, 1)).is_none());
+    }
+
+    #[test]
+    fn solves_templated_programs_by_structure() {
+        let program = templated_program();
+        let largest = find_model_number(&program, Goal::Largest).unwrap();
+        let smallest = find_model_number(&program, Goal::Smallest).unwrap();
+        assert_eq!(largest, 99491999196997);
+        assert_eq!(smallest, 31141911191611);
+        assert!(accepts(&program, &digits(largest)));
+        assert!(accepts(&program, &digits(smallest)));
+    }
+
+    #[test]
+    fn falls_back_to_searching_other_programs() {
+        // Accepts two digits that add up to 13, but doesn't match the template.
+        let program = parse("inp z\ninp x\nadd z x\nadd z -13\n").unwrap();
+        assert_eq!(part_one(&program), Some(94));
+        assert_eq!(part_two(&program), Some(49));
+        let impossible = parse("inp z\ninp x\nadd z x\nadd z -19\n").unwrap();
+        assert_eq!(part_one(&impossible), None);
+    }
+}
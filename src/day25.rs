@@ -0,0 +1,103 @@
+use crate::answer::Answer;
+use crate::error::{Error, Result};
+use crate::solver::Solver;
+use crate::utils::{parse_grid, steps_until, Grid, Position};
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Cell {
+    Empty,
+    East,
+    South,
+}
+
+/// The sea floor, whose edges wrap around to the opposite side.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SeaFloor {
+    pub cells: Grid<Cell>,
+}
+
+impl SeaFloor {
+    /// The position a cucumber in `herd` at `(r, c)` would move to.
+    fn ahead(&self, herd: Cell, (r, c): Position) -> Position {
+        match herd {
+            Cell::East => (r, (c + 1) % self.cells.width()),
+            _ => ((r + 1) % self.cells.height(), c),
+        }
+    }
+
+    /// Moves every cucumber in `herd` that has space in front of it at the same time, returning
+    /// whether any moved.
+    fn move_herd(&mut self, herd: Cell) -> bool {
+        let moving: Vec<(Position, Position)> = self
+            .cells
+            .positions()
+            .filter(|&p| self.cells[p] == herd)
+            .map(|p| (p, self.ahead(herd, p)))
+            .filter(|&(_, to)| self.cells[to] == Cell::Empty)
+            .collect();
+        for &(from, to) in &moving {
+            self.cells[from] = Cell::Empty;
+            self.cells[to] = herd;
+        }
+        !moving.is_empty()
+    }
+
+    /// Moves the east-facing herd and then the south-facing herd, returning whether any cucumber
+    /// moved.
+    pub fn step_mut(&mut self) -> bool {
+        let east = self.move_herd(Cell::East);
+        let south = self.move_herd(Cell::South);
+        east || south
+    }
+}
+
+fn parse_row(line_index: usize, line: &str) -> Result<Vec<Cell>> {
+    line.char_indices()
+        .map(|(i, ch)| match ch {
+            '.' => Ok(Cell::Empty),
+            '>' => Ok(Cell::East),
+            'v' => Ok(Cell::South),
+            _ => {
+                let token = &line[i..i + ch.len_utf8()];
+                Err(Error::parse(
+                    line_index,
+                    line,
+                    token,
+                    "expected `.`, `>` or `v`",
+                ))
+            }
+        })
+        .collect()
+}
+
+pub fn parse(input: &str) -> Result<SeaFloor> {
+    Ok(SeaFloor {
+        cells: parse_grid(input, parse_row)?,
+    })
+}
+
+/// The first step on which no sea cucumber moves.
+pub fn part_one(floor: &mut SeaFloor) -> usize {
+    steps_until(|| !floor.step_mut())
+}
+
+pub struct Day25;
+
+impl Solver for Day25 {
+    const DAY: u8 = 25;
+    const NAME: &'static str = "Sea Cucumber";
+    type Input = SeaFloor;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part_one(floor: &Self::Input) -> Answer {
+        part_one(&mut floor.clone()).into()
+    }
+
+    /// Day 25 has no second puzzle.
+    fn part_two(_floor: &Self::Input) -> Answer {
+        Answer::Text("Merry Christmas!".to_string())
+    }
+}
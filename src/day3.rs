@@ -1,4 +1,6 @@
-use crate::utils::input;
+use crate::answer::Answer;
+use crate::error::{Error, Result};
+use crate::solver::Solver;
 use std::ops::Not;
 
 const MAX_NUM_BITS: usize = 12;
@@ -27,12 +29,10 @@ fn vector_ones_set_ge(v: &[u16]) -> BitArray {
 }
 
 fn num_from_bitarray(arr: &BitArray) -> u64 {
-    (0..MAX_NUM_BITS)
-        .into_iter()
-        .fold(0u64, |acc, i| acc | ((arr[i] as u64) << i))
+    (0..MAX_NUM_BITS).fold(0u64, |acc, i| acc | ((arr[i] as u64) << i))
 }
 
-fn part_one(nums: &[u16]) -> i64 {
+pub fn part_one(nums: &[u16]) -> i64 {
     let gamma_array = vector_ones_set_ge(nums);
     let gamma_rate = num_from_bitarray(&gamma_array) as i64;
     let epsilon_array = gamma_array.map(bool::not);
@@ -59,7 +59,7 @@ fn least_common_value_in_pos(nums: &[u16], i: u8) -> u16 {
     (most_common_value_in_pos(nums, i) == 0) as u16
 }
 
-fn o2_rating(nums: &[u16]) -> i64 {
+fn o2_rating(nums: &[u16]) -> u64 {
     let mut all_nums = nums.to_vec();
     let mut i: u8 = 0;
     while all_nums.len() > 1 {
@@ -68,10 +68,10 @@ fn o2_rating(nums: &[u16]) -> i64 {
         i += 1;
     }
     assert_eq!(all_nums.len(), 1);
-    reverse_k_bit_number(all_nums[0], MAX_NUM_BITS as u8) as i64
+    reverse_k_bit_number(all_nums[0], MAX_NUM_BITS as u8) as u64
 }
 
-fn co2_rating(nums: &[u16]) -> i64 {
+fn co2_rating(nums: &[u16]) -> u64 {
     let mut all_nums = nums.to_vec();
     let mut i: u8 = 0;
     while all_nums.len() > 1 {
@@ -80,10 +80,10 @@ fn co2_rating(nums: &[u16]) -> i64 {
         i += 1;
     }
     assert_eq!(all_nums.len(), 1);
-    reverse_k_bit_number(all_nums[0], MAX_NUM_BITS as u8) as i64
+    reverse_k_bit_number(all_nums[0], MAX_NUM_BITS as u8) as u64
 }
 
-fn part_two(nums: &[u16]) -> i64 {
+pub fn part_two(nums: &[u16]) -> u64 {
     let nums: Vec<u16> = nums
         .iter()
         .copied()
@@ -93,11 +93,37 @@ fn part_two(nums: &[u16]) -> i64 {
     o2_rating(&nums) * co2_rating(&nums)
 }
 
-pub(crate) fn solve() -> (i64, i64) {
-    let inputs: Vec<u16> = input()
+/// Parses one binary number of up to 12 bits per line.
+pub fn parse(input: &str) -> Result<Vec<u16>> {
+    input
         .lines()
-        .into_iter()
-        .map(|s| u16::from_str_radix(s, 2).expect("Couldn't parse as u16"))
-        .collect();
-    (part_one(&inputs), part_two(&inputs))
+        .enumerate()
+        .map(|(i, s)| {
+            if s.len() > MAX_NUM_BITS {
+                let reason = format!("expected at most {} bits", MAX_NUM_BITS);
+                return Err(Error::line(i, s, reason));
+            }
+            u16::from_str_radix(s, 2).map_err(|e| Error::line(i, s, e))
+        })
+        .collect()
+}
+
+pub struct Day3;
+
+impl Solver for Day3 {
+    const DAY: u8 = 3;
+    const NAME: &'static str = "Binary Diagnostic";
+    type Input = Vec<u16>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part_one(nums: &Self::Input) -> Answer {
+        part_one(nums).into()
+    }
+
+    fn part_two(nums: &Self::Input) -> Answer {
+        part_two(nums).into()
+    }
 }
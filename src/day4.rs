@@ -1,11 +1,16 @@
-use crate::utils::input;
+use crate::answer::Answer;
+use crate::error::{Error, Result};
+use crate::solver::Solver;
+use crate::utils::parse_tokens;
 use std::collections::HashMap;
 use std::convert::{identity, TryInto};
+use std::str::FromStr;
 
 const ROW_SIZE: usize = 5;
 
+/// A 5x5 bingo board, tracking which of its cells have been marked.
 #[derive(Clone)]
-struct Board {
+pub struct Board {
     cells: [u64; ROW_SIZE * ROW_SIZE],
     marked: [bool; ROW_SIZE * ROW_SIZE],
     value_to_cell: HashMap<u64, usize>,
@@ -13,25 +18,34 @@ struct Board {
 }
 
 impl Board {
-    fn from_str_rows(rows: &[&str]) -> Self {
-        assert_eq!(rows.len(), 5);
-        let cells: Vec<u64> = rows
-            .iter()
-            .flat_map(|r| {
-                r.split_whitespace()
-                    .map(|s| s.parse::<u64>().expect("Failed to parse a cell as u64"))
-            })
-            .collect();
+    /// Parses a board from its rows, the first of which is at index `first_line` of the input.
+    fn from_str_rows(rows: &[&str], first_line: usize) -> Result<Self> {
+        let mut cells: Vec<u64> = Vec::with_capacity(ROW_SIZE * ROW_SIZE);
+        for (i, row) in rows.iter().enumerate() {
+            let row_cells: Vec<u64> = parse_tokens(first_line + i, row, row.split_whitespace())?;
+            if row_cells.len() != ROW_SIZE {
+                let reason = format!("expected {} cells in a board row", ROW_SIZE);
+                return Err(Error::line(first_line + i, row, reason));
+            }
+            cells.extend(row_cells);
+        }
+        if rows.len() != ROW_SIZE {
+            let reason = format!("expected {} rows in a board", ROW_SIZE);
+            return Err(match rows.last() {
+                Some(last) => Error::line(first_line + rows.len() - 1, last, reason),
+                None => Error::line(first_line, "", reason),
+            });
+        }
         let value_to_cell = cells.iter().enumerate().map(|(i, &n)| (n, i)).collect();
         let marked = [false; ROW_SIZE * ROW_SIZE];
-        Self {
+        Ok(Self {
             cells: cells.try_into().expect("Cells had wrong length"),
             value_to_cell,
             marked,
-        }
+        })
     }
 
-    fn is_win(&self) -> bool {
+    pub fn is_win(&self) -> bool {
         // Check for bingo in a row
         if (0..ROW_SIZE).any(|r| {
             self.marked
@@ -58,13 +72,13 @@ impl Board {
         false
     }
 
-    fn draw_number(&mut self, n: u64) {
+    pub fn draw_number(&mut self, n: u64) {
         if let Some(&i) = self.value_to_cell.get(&n) {
             self.marked[i] = true;
         }
     }
 
-    fn score(&self, last_n: u64) -> i64 {
+    pub fn score(&self, last_n: u64) -> i64 {
         last_n as i64
             * self
                 .cells
@@ -83,23 +97,38 @@ impl Board {
     }
 }
 
-fn parse_input(lines: &[&str]) -> (Vec<u64>, Vec<Board>) {
-    let numbers: Vec<u64> = lines
-        .get(0)
-        .expect("Should have at least 1 line")
-        .split(',')
-        .map(|s| s.parse().expect("Failed to parse first row as all numbers"))
-        .collect();
+impl FromStr for Board {
+    type Err = Error;
+
+    /// Parses a board from five lines of five whitespace-separated numbers.
+    fn from_str(s: &str) -> Result<Self> {
+        let rows: Vec<&str> = s.lines().collect();
+        Board::from_str_rows(&rows, 0)
+    }
+}
+
+/// Parses the drawn numbers and every board that follows them.
+pub fn parse(input: &str) -> Result<(Vec<u64>, Vec<Board>)> {
+    let lines: Vec<&str> = input.lines().collect();
+    parse_input(&lines)
+}
+
+fn parse_input(lines: &[&str]) -> Result<(Vec<u64>, Vec<Board>)> {
+    let first = lines
+        .first()
+        .ok_or_else(|| Error::line(0, "", "expected a line of drawn numbers"))?;
+    let numbers: Vec<u64> = parse_tokens(0, first, first.split(','))?;
     // Skip the next line: it's blank as per the spec
     let boards: Vec<Board> = lines[1..]
-        .chunks(6)
-        .filter(|chunk| chunk.len() != 1)
-        .map(|rows| Board::from_str_rows(&rows[1..]))
-        .collect();
-    (numbers, boards)
+        .chunks(ROW_SIZE + 1)
+        .enumerate()
+        .filter(|(_i, chunk)| chunk.len() != 1)
+        .map(|(i, rows)| Board::from_str_rows(&rows[1..], 2 + i * (ROW_SIZE + 1)))
+        .collect::<Result<_>>()?;
+    Ok((numbers, boards))
 }
 
-fn part_one(numbers: &[u64], mut boards: Vec<Board>) -> i64 {
+pub fn part_one(numbers: &[u64], mut boards: Vec<Board>) -> i64 {
     let mut i = 0;
     while !boards.iter().any(Board::is_win) {
         boards.iter_mut().for_each(|b| b.draw_number(numbers[i]));
@@ -111,7 +140,7 @@ fn part_one(numbers: &[u64], mut boards: Vec<Board>) -> i64 {
     first_winning_board.score(numbers[last_i])
 }
 
-fn part_two(numbers: &[u64], mut boards: Vec<Board>) -> i64 {
+pub fn part_two(numbers: &[u64], mut boards: Vec<Board>) -> i64 {
     let mut i = 0;
     while !boards.iter().all(Board::is_win) {
         boards.retain(|b| !b.is_win()); // Reduce only to boards that haven't been won
@@ -124,12 +153,22 @@ fn part_two(numbers: &[u64], mut boards: Vec<Board>) -> i64 {
     last_winning_board.score(numbers[last_i])
 }
 
-pub(crate) fn solve() -> (i64, i64) {
-    let input = input();
-    let lines: Vec<&str> = input.lines().collect();
-    let (numbers, boards) = parse_input(&lines);
-    (
-        part_one(&numbers, boards.clone()),
-        part_two(&numbers, boards),
-    )
+pub struct Day4;
+
+impl Solver for Day4 {
+    const DAY: u8 = 4;
+    const NAME: &'static str = "Giant Squid";
+    type Input = (Vec<u64>, Vec<Board>);
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part_one((numbers, boards): &Self::Input) -> Answer {
+        part_one(numbers, boards.clone()).into()
+    }
+
+    fn part_two((numbers, boards): &Self::Input) -> Answer {
+        part_two(numbers, boards.clone()).into()
+    }
 }
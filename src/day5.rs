@@ -1,3 +1,203 @@
-pub(crate) fn solve() -> (i64, i64) {
-    (0, 0)
+use crate::answer::Answer;
+use crate::error::Result;
+use crate::solver::Solver;
+use crate::utils::parse_input;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// The largest number of cells for which overlaps are counted on a dense grid rather than in a
+/// hash map.
+const DENSE_GRID_LIMIT: i64 = 1 << 24;
+
+pub type Point = (i64, i64);
+
+/// A line of hydrothermal vents, which is horizontal, vertical or at 45 degrees.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Segment {
+    pub start: Point,
+    pub end: Point,
+}
+
+#[derive(Debug)]
+pub struct ParseSegmentError;
+
+impl Display for ParseSegmentError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Failed to parse a horizontal, vertical or diagonal `x1,y1 -> x2,y2` segment"
+        )
+    }
+}
+
+impl std::error::Error for ParseSegmentError {}
+
+impl FromStr for Segment {
+    type Err = ParseSegmentError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        fn point(s: &str) -> std::result::Result<Point, ParseSegmentError> {
+            let (x, y) = s.trim().split_once(',').ok_or(ParseSegmentError)?;
+            let x = x.parse().map_err(|_| ParseSegmentError)?;
+            let y = y.parse().map_err(|_| ParseSegmentError)?;
+            Ok((x, y))
+        }
+        let (start, end) = s.split_once("->").ok_or(ParseSegmentError)?;
+        let segment = Segment {
+            start: point(start)?,
+            end: point(end)?,
+        };
+        if segment.is_axis_aligned() || segment.is_diagonal() {
+            Ok(segment)
+        } else {
+            Err(ParseSegmentError)
+        }
+    }
+}
+
+impl Segment {
+    pub fn is_axis_aligned(&self) -> bool {
+        self.start.0 == self.end.0 || self.start.1 == self.end.1
+    }
+
+    pub fn is_diagonal(&self) -> bool {
+        let (dx, dy) = (self.end.0 - self.start.0, self.end.1 - self.start.1);
+        dx != 0 && dx.abs() == dy.abs()
+    }
+
+    /// Every point covered by the segment, from `start` to `end` inclusive.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let (dx, dy) = (self.end.0 - self.start.0, self.end.1 - self.start.1);
+        let (step_x, step_y) = (dx.signum(), dy.signum());
+        let (x, y) = self.start;
+        (0..=dx.abs().max(dy.abs())).map(move |i| (x + i * step_x, y + i * step_y))
+    }
+}
+
+/// Counts the points covered by at least two of `segments`.
+pub fn count_overlaps(segments: &[&Segment]) -> usize {
+    if segments.is_empty() {
+        return 0;
+    }
+    let xs = segments.iter().flat_map(|s| [s.start.0, s.end.0]);
+    let ys = segments.iter().flat_map(|s| [s.start.1, s.end.1]);
+    let (min_x, max_x) = (xs.clone().min().unwrap(), xs.max().unwrap());
+    let (min_y, max_y) = (ys.clone().min().unwrap(), ys.max().unwrap());
+    let (width, height) = (max_x - min_x + 1, max_y - min_y + 1);
+    if width.saturating_mul(height) <= DENSE_GRID_LIMIT {
+        let mut grid = vec![0u8; (width * height) as usize];
+        for (x, y) in segments.iter().flat_map(|s| s.points()) {
+            let cell = &mut grid[((y - min_y) * width + (x - min_x)) as usize];
+            *cell = cell.saturating_add(1);
+        }
+        grid.iter().filter(|&&n| n >= 2).count()
+    } else {
+        count_overlaps_sparse(segments)
+    }
+}
+
+fn count_overlaps_sparse(segments: &[&Segment]) -> usize {
+    let mut covered: HashMap<Point, u32> = HashMap::new();
+    for point in segments.iter().flat_map(|s| s.points()) {
+        *covered.entry(point).or_default() += 1;
+    }
+    covered.values().filter(|&&n| n >= 2).count()
+}
+
+pub fn parse(input: &str) -> Result<Vec<Segment>> {
+    parse_input(input)
+}
+
+pub fn part_one(segments: &[Segment]) -> usize {
+    let aligned: Vec<&Segment> = segments.iter().filter(|s| s.is_axis_aligned()).collect();
+    count_overlaps(&aligned)
+}
+
+pub fn part_two(segments: &[Segment]) -> usize {
+    let all: Vec<&Segment> = segments.iter().collect();
+    count_overlaps(&all)
+}
+
+pub struct Day5;
+
+impl Solver for Day5 {
+    const DAY: u8 = 5;
+    const NAME: &'static str = "Hydrothermal Venture";
+    type Input = Vec<Segment>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part_one(segments: &Self::Input) -> Answer {
+        part_one(segments).into()
+    }
+
+    fn part_two(segments: &Self::Input) -> Answer {
+        part_two(segments).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "0,9 -> 5,9
+8,0 -> 0,8
+9,4 -> 3,4
+2,2 -> 2,1
+7,0 -> 7,4
+6,4 -> 2,0
+0,9 -> 2,9
+3,4 -> 1,4
+0,0 -> 8,8
+5,5 -> 8,2
+";
+
+    #[test]
+    fn parses_segments() {
+        let segments = parse(EXAMPLE).unwrap();
+        assert_eq!(segments.len(), 10);
+        assert_eq!(
+            segments[1],
+            Segment {
+                start: (8, 0),
+                end: (0, 8)
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_segments() {
+        assert!(parse("0,9 -> 5").is_err());
+        assert!(parse("0,9 -> 5,7").is_err());
+    }
+
+    #[test]
+    fn walks_diagonals_in_both_directions() {
+        let segment: Segment = "3,1 -> 1,3".parse().unwrap();
+        let points: Vec<Point> = segment.points().collect();
+        assert_eq!(points, vec![(3, 1), (2, 2), (1, 3)]);
+    }
+
+    #[test]
+    fn example_part_one() {
+        assert_eq!(part_one(&parse(EXAMPLE).unwrap()), 5);
+    }
+
+    #[test]
+    fn example_part_two() {
+        assert_eq!(part_two(&parse(EXAMPLE).unwrap()), 12);
+    }
+
+    #[test]
+    fn sparse_grid_agrees_with_dense_grid() {
+        let segments = parse(EXAMPLE).unwrap();
+        let all: Vec<&Segment> = segments.iter().collect();
+        assert_eq!(count_overlaps_sparse(&all), count_overlaps(&all));
+        // Spread far enough apart to fall back to the sparse grid.
+        let far = parse("0,0 -> 0,2\n0,1 -> 2,1\n100000000,0 -> 100000000,1").unwrap();
+        assert_eq!(part_one(&far), 1);
+    }
 }
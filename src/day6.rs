@@ -1,7 +1,10 @@
-use crate::utils::input;
+use crate::answer::Answer;
+use crate::error::{Error, Result};
+use crate::solver::Solver;
+use crate::utils::parse_tokens;
 use std::collections::HashMap;
 
-fn simulate(inputs: [u64; 9], n_days: u16) -> i64 {
+pub fn simulate(inputs: [u64; 9], n_days: u16) -> u64 {
     let mut all_fish: [u64; 9] = inputs;
     for _ in 0..n_days {
         let to_reset = all_fish[0];
@@ -11,17 +14,14 @@ fn simulate(inputs: [u64; 9], n_days: u16) -> i64 {
         all_fish[8] = to_reset;
         all_fish[6] += to_reset;
     }
-    all_fish.iter().sum::<u64>() as i64
+    all_fish.iter().sum()
 }
 
-pub(crate) fn solve() -> (i64, i64) {
-    let inputs = input();
-    let parsed_inputs = inputs
-        .trim()
-        .split(',')
-        .map(str::parse)
-        .collect::<Result<Vec<u8>, _>>()
-        .expect("Failed to parse input...");
+/// Parses the comma-separated fish timers into the number of fish at each timer value.
+pub fn parse(inputs: &str) -> Result<[u64; 9]> {
+    let line = inputs.lines().next().unwrap_or_default();
+    let tokens: Vec<&str> = line.trim().split(',').collect();
+    let parsed_inputs: Vec<u8> = parse_tokens(0, line, tokens.iter().copied())?;
     let frequencies: HashMap<u8, u64> =
         parsed_inputs
             .into_iter()
@@ -31,10 +31,39 @@ pub(crate) fn solve() -> (i64, i64) {
             });
     let mut fish_frequencies = [0u64; 9];
     for (i, n) in frequencies {
+        if i as usize >= fish_frequencies.len() {
+            let token = tokens.iter().find(|t| t.parse() == Ok(i)).unwrap_or(&line);
+            return Err(Error::parse(0, line, token, "timer must be at most 8"));
+        }
         fish_frequencies[i as usize] = n;
     }
-    (
-        simulate(fish_frequencies, 80),
-        simulate(fish_frequencies, 256),
-    )
+    Ok(fish_frequencies)
+}
+
+pub fn part_one(fish_frequencies: &[u64; 9]) -> u64 {
+    simulate(*fish_frequencies, 80)
+}
+
+pub fn part_two(fish_frequencies: &[u64; 9]) -> u64 {
+    simulate(*fish_frequencies, 256)
+}
+
+pub struct Day6;
+
+impl Solver for Day6 {
+    const DAY: u8 = 6;
+    const NAME: &'static str = "Lanternfish";
+    type Input = [u64; 9];
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part_one(fish_frequencies: &Self::Input) -> Answer {
+        part_one(fish_frequencies).into()
+    }
+
+    fn part_two(fish_frequencies: &Self::Input) -> Answer {
+        part_two(fish_frequencies).into()
+    }
 }
@@ -1,9 +1,12 @@
-use crate::utils::input;
+use crate::answer::Answer;
+use crate::error::Result;
+use crate::solver::Solver;
+use crate::utils::parse_tokens;
 
-fn part_one(sorted_inputs: &[u64]) -> i64 {
+pub fn part_one(sorted_inputs: &[u64]) -> i64 {
     let n = sorted_inputs.len();
     let median = {
-        if sorted_inputs.len() % 2 == 0 {
+        if sorted_inputs.len().is_multiple_of(2) {
             ((sorted_inputs[n / 2 - 1] + sorted_inputs[n / 2]) as f64 / 2f64) as u64
         } else {
             sorted_inputs[n / 2]
@@ -11,13 +14,13 @@ fn part_one(sorted_inputs: &[u64]) -> i64 {
     };
     sorted_inputs
         .iter()
-        .map(|&num| (num as i64 - median as i64).abs() as u64)
+        .map(|&num| (num as i64 - median as i64).unsigned_abs())
         .sum::<u64>() as i64
 }
 
-fn part_two(inputs: &[u64]) -> i64 {
+pub fn part_two(inputs: &[u64]) -> i64 {
     fn distance_metric(a: u64, b: u64) -> u64 {
-        let abs_diff = (a as i64 - b as i64).abs() as u64;
+        let abs_diff = (a as i64 - b as i64).unsigned_abs();
         (abs_diff * (abs_diff + 1)) / 2
     }
     let min = *inputs.iter().min().expect("Expected at least one element");
@@ -28,15 +31,30 @@ fn part_two(inputs: &[u64]) -> i64 {
         .expect("Expected at least one element") as i64
 }
 
-pub(crate) fn solve() -> (i64, i64) {
-    let inputs = input();
-    let mut parsed_inputs = inputs
-        .trim()
-        .split(',')
-        .map(str::parse)
-        .collect::<Result<Vec<u64>, _>>()
-        .expect("Failed to parse input...");
+/// Parses the comma-separated crab positions, sorted in ascending order.
+pub fn parse(input: &str) -> Result<Vec<u64>> {
+    let line = input.lines().next().unwrap_or_default();
+    let mut parsed_inputs: Vec<u64> = parse_tokens(0, line, line.trim().split(','))?;
     parsed_inputs.sort_unstable();
-    let parsed_inputs = parsed_inputs;
-    (part_one(&parsed_inputs), part_two(&parsed_inputs))
+    Ok(parsed_inputs)
+}
+
+pub struct Day7;
+
+impl Solver for Day7 {
+    const DAY: u8 = 7;
+    const NAME: &'static str = "The Treachery of Whales";
+    type Input = Vec<u64>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part_one(sorted_inputs: &Self::Input) -> Answer {
+        part_one(sorted_inputs).into()
+    }
+
+    fn part_two(sorted_inputs: &Self::Input) -> Answer {
+        part_two(sorted_inputs).into()
+    }
 }
@@ -0,0 +1,152 @@
+use crate::answer::Answer;
+use crate::error::{Error, Result};
+use crate::solver::Solver;
+
+/// A set of lit segments `a` to `g`, one bit per segment with `a` as the lowest bit.
+pub type Segments = u8;
+
+/// One display's ten unique signal patterns and the four-digit value it shows.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Entry {
+    pub patterns: [Segments; 10],
+    pub outputs: [Segments; 4],
+}
+
+impl Entry {
+    /// Works out which pattern lights up each digit, returning them indexed by digit.
+    pub fn deduce(&self) -> Option<[Segments; 10]> {
+        let with_len = |n: u32| {
+            self.patterns
+                .iter()
+                .copied()
+                .filter(move |p| p.count_ones() == n)
+        };
+        let unique = |n: u32| {
+            let mut matching = with_len(n);
+            matching.next().filter(|_| matching.next().is_none())
+        };
+        let mut digits = [0; 10];
+        digits[1] = unique(2)?;
+        digits[4] = unique(4)?;
+        digits[7] = unique(3)?;
+        digits[8] = unique(7)?;
+        let contains = |p: Segments, q: Segments| p & q == q;
+        for p in with_len(6) {
+            let digit = if contains(p, digits[4]) {
+                9
+            } else if contains(p, digits[1]) {
+                0
+            } else {
+                6
+            };
+            digits[digit] = p;
+        }
+        for p in with_len(5) {
+            let digit = if contains(p, digits[1]) {
+                3
+            } else if (p & digits[4]).count_ones() == 3 {
+                5
+            } else {
+                2
+            };
+            digits[digit] = p;
+        }
+        // Every digit must have been found exactly once, and all of them must be distinct.
+        let distinct = (0..10).all(|i| (0..i).all(|j| digits[i] != digits[j]));
+        if digits.contains(&0) || !distinct {
+            return None;
+        }
+        Some(digits)
+    }
+
+    /// The four-digit value shown, if the patterns and outputs are consistent.
+    pub fn decode(&self) -> Option<u32> {
+        let digits = self.deduce()?;
+        self.outputs.iter().try_fold(0, |acc, output| {
+            let digit = digits.iter().position(|d| d == output)?;
+            Some(10 * acc + digit as u32)
+        })
+    }
+}
+
+fn parse_segments(line_index: usize, line: &str, word: &str) -> Result<Segments> {
+    word.bytes().try_fold(0, |acc, b| match b {
+        b'a'..=b'g' if acc & (1 << (b - b'a')) == 0 => Ok(acc | 1 << (b - b'a')),
+        _ => Err(Error::parse(
+            line_index,
+            line,
+            word,
+            "expected distinct segments a-g",
+        )),
+    })
+}
+
+fn parse_entry(line_index: usize, line: &str) -> Result<Entry> {
+    let (patterns, outputs) = line
+        .split_once('|')
+        .ok_or_else(|| Error::line(line_index, line, "expected `patterns | outputs`"))?;
+    let words = |s: &str| -> Result<Vec<Segments>> {
+        s.split_whitespace()
+            .map(|word| parse_segments(line_index, line, word))
+            .collect()
+    };
+    let entry = Entry {
+        patterns: words(patterns)?
+            .try_into()
+            .map_err(|_| Error::parse(line_index, line, patterns, "expected 10 patterns"))?,
+        outputs: words(outputs)?
+            .try_into()
+            .map_err(|_| Error::parse(line_index, line, outputs, "expected 4 output digits"))?,
+    };
+    if entry.decode().is_none() {
+        return Err(Error::line(
+            line_index,
+            line,
+            "patterns and outputs don't form a consistent set of digits",
+        ));
+    }
+    Ok(entry)
+}
+
+pub fn parse(input: &str) -> Result<Vec<Entry>> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| parse_entry(i, line))
+        .collect()
+}
+
+/// Counts the output digits that are 1, 4, 7 or 8, which each light a unique number of segments.
+pub fn part_one(entries: &[Entry]) -> usize {
+    entries
+        .iter()
+        .flat_map(|e| e.outputs.iter())
+        .filter(|o| matches!(o.count_ones(), 2 | 3 | 4 | 7))
+        .count()
+}
+
+/// The sum of every entry's output value, or `None` if an entry can't be decoded. Entries from
+/// [`parse`] always can be.
+pub fn part_two(entries: &[Entry]) -> Option<u64> {
+    entries.iter().map(|e| e.decode().map(u64::from)).sum()
+}
+
+pub struct Day8;
+
+impl Solver for Day8 {
+    const DAY: u8 = 8;
+    const NAME: &'static str = "Seven Segment Search";
+    type Input = Vec<Entry>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part_one(entries: &Self::Input) -> Answer {
+        part_one(entries).into()
+    }
+
+    fn part_two(entries: &Self::Input) -> Answer {
+        Answer::or_text(part_two(entries), "undecodable entries")
+    }
+}
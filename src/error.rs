@@ -0,0 +1,96 @@
+use std::fmt::{Display, Formatter};
+
+use crate::utils::InputError;
+
+/// Any failure that stops a day from producing its answers.
+#[derive(Debug)]
+pub enum Error {
+    Input(InputError),
+    Parse(ParseError),
+}
+
+/// Malformed puzzle input, pinpointed to the offending text.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseError {
+    /// Filled in by the runner, since the shared parsing helpers don't know which day they serve.
+    pub day: Option<u8>,
+    /// 1-based line number within the input.
+    pub line: usize,
+    /// 1-based column, counted in characters, at which `text` starts.
+    pub column: usize,
+    pub text: String,
+    pub reason: String,
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+    /// Reports that `token`, which must be a subslice of `line`, could not be parsed.
+    ///
+    /// `line_index` is the 0-based index of `line` within the input, as produced by `enumerate`.
+    pub(crate) fn parse(line_index: usize, line: &str, token: &str, reason: impl Display) -> Self {
+        let offset = (token.as_ptr() as usize).wrapping_sub(line.as_ptr() as usize);
+        debug_assert!(offset <= line.len(), "token must lie within line");
+        let column = line.get(..offset).map_or(0, |s| s.chars().count()) + 1;
+        Error::Parse(ParseError {
+            day: None,
+            line: line_index + 1,
+            column,
+            text: token.to_string(),
+            reason: reason.to_string(),
+        })
+    }
+
+    /// Reports a problem with the whole of `line`.
+    pub(crate) fn line(line_index: usize, line: &str, reason: impl Display) -> Self {
+        Self::parse(line_index, line, line, reason)
+    }
+
+    /// Attributes a parse error to `day`, if it isn't already attributed to one.
+    pub(crate) fn in_day(self, day: u8) -> Self {
+        match self {
+            Error::Parse(e) => Error::Parse(ParseError {
+                day: e.day.or(Some(day)),
+                ..e
+            }),
+            e => e,
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if let Some(day) = self.day {
+            write!(f, "day {}, ", day)?;
+        }
+        write!(
+            f,
+            "line {}, column {}: {}: {:?}",
+            self.line, self.column, self.reason, self.text
+        )
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Input(e) => write!(f, "{}", e),
+            Error::Parse(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Input(e) => Some(e),
+            Error::Parse(_) => None,
+        }
+    }
+}
+
+impl From<InputError> for Error {
+    fn from(e: InputError) -> Self {
+        Error::Input(e)
+    }
+}
@@ -1,35 +1,285 @@
-#![feature(array_zip)]
-
-mod day1;
-mod day10;
-mod day11;
-mod day2;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
-mod day9;
-mod utils;
+use aoc2021::answer::Answer;
+use aoc2021::answers;
+use aoc2021::answers::{Answers, Verdict, DEFAULT_ANSWERS_PATH};
+use aoc2021::bench::{self, Baseline, Stats, STAGES};
+use aoc2021::error::{self, Error};
+use aoc2021::solver::{self, Report, Runner};
+use aoc2021::utils::{input, InputSource};
+use std::fmt::Display;
+use std::path::PathBuf;
 
-fn main() {
-    let day = std::env::args()
-        .nth(1)
-        .expect("Expected one argument for day")
-        .parse::<u8>()
-        .expect("Could not parse provided day as u8");
-    let (pt1, pt2) = match day {
-        1 => day1::solve(),
-        2 => day2::solve(),
-        3 => day3::solve(),
-        4 => day4::solve(),
-        5 => day5::solve(),
-        6 => day6::solve(),
-        7 => day7::solve(),
-        9 => day9::solve(),
-        10 => day10::solve(),
-        11 => day11::solve(),
-        _ => panic!(),
+const DEFAULT_BENCH_ITERATIONS: usize = 100;
+
+/// Prints `message` to stderr and exits with a failure status.
+fn fail(message: impl Display) -> ! {
+    eprintln!("{}", message);
+    std::process::exit(1);
+}
+
+fn run_with_input(s: &dyn Runner, options: &Options) -> error::Result<Report> {
+    let input = input(s.day(), &options.input)?;
+    s.run(&input)
+}
+
+fn list() {
+    for s in solver::SOLVERS {
+        println!("Day {:>2} | {}", s.day(), s.name());
+    }
+}
+
+fn run(day: u8, options: &Options) {
+    match solver::find(day) {
+        Some(s) => {
+            let report = run_with_input(s, options).unwrap_or_else(|e| fail(e));
+            println!(
+                "Day {} | Part 1 {} | Part 2 {}",
+                day,
+                inline(&report.part_one),
+                inline(&report.part_two)
+            );
+            print_multiline(day, &[&report.part_one, &report.part_two]);
+        }
+        None => fail(format!("day {} not implemented", day)),
+    }
+}
+
+/// Runs every registered day against its input file and prints the results as a table.
+fn all(options: &Options) {
+    if !matches!(options.input, InputSource::Directory(_)) {
+        fail(String::from(
+            "`all` reads one file per day: use --input-dir rather than --input",
+        ));
+    }
+    let header = ["Day", "Name", "Part 1", "Time", "Part 2", "Time", "Parse"].map(String::from);
+    let mut rows = vec![header];
+    let mut errors = Vec::new();
+    let mut reports = Vec::new();
+    for s in solver::SOLVERS {
+        let day = s.day().to_string();
+        let name = s.name().to_string();
+        match run_with_input(*s, options) {
+            Ok(report) => {
+                rows.push([
+                    day,
+                    name,
+                    inline(&report.part_one),
+                    format!("{:.2?}", report.part_one_time),
+                    inline(&report.part_two),
+                    format!("{:.2?}", report.part_two_time),
+                    format!("{:.2?}", report.parse_time),
+                ]);
+                reports.push((s.day(), report));
+            }
+            Err(e) => {
+                errors.push(e);
+                let blank = || String::from("-");
+                rows.push([day, name, blank(), blank(), blank(), blank(), blank()]);
+            }
+        }
+    }
+    print_table(&rows);
+    for (day, report) in reports {
+        print_multiline(day, &[&report.part_one, &report.part_two]);
+    }
+    for e in errors {
+        eprintln!("{}", e);
+    }
+}
+
+/// Formats an answer to fit on one line, deferring multi-line answers to [`print_multiline`].
+fn inline(answer: &Answer) -> String {
+    if answer.is_multiline() {
+        String::from("(see below)")
+    } else {
+        answer.to_string()
+    }
+}
+
+fn print_multiline(day: u8, answers: &[&Answer]) {
+    for (i, answer) in answers.iter().enumerate() {
+        if answer.is_multiline() {
+            println!("\nDay {} | Part {}\n{}", day, i + 1, answer);
+        }
+    }
+}
+
+fn print_table<const N: usize>(rows: &[[String; N]]) {
+    let mut widths = [0; N];
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    for row in rows {
+        let cells: Vec<String> = row
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect();
+        println!("{}", cells.join(" | ").trim_end());
+    }
+}
+
+/// Flags shared by every subcommand; each subcommand only reads the ones relevant to it.
+struct Options {
+    positional: Vec<String>,
+    input: InputSource,
+    iterations: usize,
+    save: Option<PathBuf>,
+    compare: Option<PathBuf>,
+    answers: PathBuf,
+}
+
+impl Options {
+    fn from_args(args: impl Iterator<Item = String>) -> Self {
+        let mut options = Options {
+            positional: Vec::new(),
+            input: InputSource::default(),
+            iterations: DEFAULT_BENCH_ITERATIONS,
+            save: None,
+            compare: None,
+            answers: PathBuf::from(DEFAULT_ANSWERS_PATH),
+        };
+        let mut args = args.peekable();
+        while let Some(arg) = args.next() {
+            if arg == "-" {
+                options.input = InputSource::Stdin;
+                continue;
+            }
+            if !arg.starts_with("--") {
+                options.positional.push(arg);
+                continue;
+            }
+            let value = args
+                .next()
+                .unwrap_or_else(|| fail(format!("Expected a value after {}", arg)));
+            match arg.as_str() {
+                "--input" => options.input = InputSource::from_arg(&value),
+                "--input-dir" => options.input = InputSource::Directory(PathBuf::from(value)),
+                "--iterations" => {
+                    options.iterations = value
+                        .parse()
+                        .unwrap_or_else(|_| fail(format!("Could not parse {} as usize", value)))
+                }
+                "--save" => options.save = Some(PathBuf::from(value)),
+                "--compare" => options.compare = Some(PathBuf::from(value)),
+                "--answers" => options.answers = PathBuf::from(value),
+                _ => fail(format!("Unknown option {}", arg)),
+            }
+        }
+        if options.iterations == 0 {
+            fail(String::from("Need at least one iteration"));
+        }
+        options
+    }
+}
+
+/// Benchmarks each stage of `day`, optionally saving to or comparing against a baseline file.
+fn bench(day: u8, options: &Options) {
+    let s = solver::find(day).unwrap_or_else(|| fail(format!("day {} not implemented", day)));
+    let input = input(day, &options.input).unwrap_or_else(|e| fail(e));
+    let load = |path: &PathBuf| {
+        Baseline::load(path).unwrap_or_else(|e| fail(format!("{}: {}", path.display(), e)))
     };
-    println!("Day {} | Part 1 {} | Part 2 {}", day, pt1, pt2);
+    let baseline = options.compare.as_ref().map(load);
+    let mut saved = match &options.save {
+        Some(path) if path.exists() => load(path),
+        _ => Baseline::default(),
+    };
+    println!(
+        "Day {} | {} | {} iterations",
+        day,
+        s.name(),
+        options.iterations
+    );
+    let samples = s
+        .bench(&input, options.iterations)
+        .unwrap_or_else(|e| fail(e));
+    for (stage, times) in STAGES.iter().zip(&samples.stages) {
+        let stats = Stats::from_samples(times);
+        let comparison = baseline
+            .as_ref()
+            .and_then(|b| b.get(day, stage))
+            .map(|before| format!(" | {}", bench::compare(stats, before)))
+            .unwrap_or_default();
+        println!("{:<8} | {}{}", stage, stats, comparison);
+        saved.insert(day, stage, stats);
+    }
+    if let Some(path) = &options.save {
+        saved
+            .save(path)
+            .unwrap_or_else(|e| fail(format!("{}: {}", path.display(), e)));
+    }
+}
+
+/// Checks every registered day (or just `day`) against the answers file, exiting non-zero if
+/// any computed answer differs from the stored one.
+fn verify(day: Option<u8>, options: &Options) {
+    let answers = Answers::load(&options.answers).unwrap_or_else(|e| fail(e));
+    let solvers: Vec<_> = match day {
+        Some(day) => {
+            vec![solver::find(day).unwrap_or_else(|| fail(format!("day {} not implemented", day)))]
+        }
+        None => solver::SOLVERS.to_vec(),
+    };
+    let mut failed = false;
+    for s in solvers {
+        let report = match run_with_input(s, options) {
+            Ok(report) => report,
+            Err(e @ Error::Input(_)) => {
+                println!("Day {:>2} | SKIP | {}", s.day(), e);
+                continue;
+            }
+            Err(e) => {
+                failed = true;
+                println!("Day {:>2} | ERROR | {}", s.day(), e);
+                continue;
+            }
+        };
+        for (part, actual) in [(1, &report.part_one), (2, &report.part_two)] {
+            let expected = answers.get(s.day(), part);
+            let actual = actual.to_string();
+            let verdict = answers::check(expected, &actual);
+            match verdict {
+                Verdict::Fail => {
+                    failed = true;
+                    println!(
+                        "Day {:>2} | Part {} | {} | expected {}, got {}",
+                        s.day(),
+                        part,
+                        verdict,
+                        expected.unwrap_or_default(),
+                        actual
+                    );
+                }
+                _ => println!("Day {:>2} | Part {} | {}", s.day(), part, verdict),
+            }
+        }
+    }
+    if failed {
+        std::process::exit(1);
+    }
+}
+
+fn parse_day(arg: &str) -> u8 {
+    arg.parse::<u8>()
+        .unwrap_or_else(|_| fail(format!("Could not parse {} as a day", arg)))
+}
+
+fn main() {
+    let options = Options::from_args(std::env::args().skip(1));
+    let positional: Vec<&str> = options.positional.iter().map(String::as_str).collect();
+    match positional.as_slice() {
+        ["list"] => list(),
+        ["all"] => all(&options),
+        ["bench", day] => bench(parse_day(day), &options),
+        ["verify"] => verify(None, &options),
+        ["verify", day] => verify(Some(parse_day(day)), &options),
+        [day] => run(parse_day(day), &options),
+        _ => fail(String::from(
+            "Usage: aoc2021 <day | all | bench <day> | verify [day] | list> [--input <path | ->] \
+             [--input-dir <dir>] [--iterations <n>] [--save <path>] [--compare <path>] \
+             [--answers <path>]",
+        )),
+    }
 }
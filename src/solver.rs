@@ -0,0 +1,119 @@
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use crate::answer::Answer;
+use crate::bench::Samples;
+use crate::error::Result;
+
+use crate::{
+    day1, day10, day11, day12, day13, day14, day15, day16, day17, day18, day19, day2, day20, day21,
+    day22, day23, day24, day25, day3, day4, day5, day6, day7, day8, day9,
+};
+
+/// A single day's puzzle: how to parse its input and how to answer both parts from it.
+pub trait Solver {
+    const DAY: u8;
+    const NAME: &'static str;
+    type Input;
+
+    fn parse(input: &str) -> Result<Self::Input>;
+    fn part_one(input: &Self::Input) -> Answer;
+    fn part_two(input: &Self::Input) -> Answer;
+}
+
+/// Object-safe view of a [`Solver`], so that every day can live in one registry.
+pub trait Runner: Sync {
+    fn day(&self) -> u8;
+    fn name(&self) -> &'static str;
+    fn run(&self, input: &str) -> Result<Report>;
+    /// Times parsing, part one and part two separately, `iterations` times each.
+    fn bench(&self, input: &str, iterations: usize) -> Result<Samples>;
+}
+
+/// The answers to both parts of a day, along with how long each stage took.
+pub struct Report {
+    pub part_one: Answer,
+    pub part_two: Answer,
+    pub parse_time: Duration,
+    pub part_one_time: Duration,
+    pub part_two_time: Duration,
+}
+
+fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let value = f();
+    (value, start.elapsed())
+}
+
+impl<S: Solver + Sync> Runner for S {
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn name(&self) -> &'static str {
+        S::NAME
+    }
+
+    fn run(&self, input: &str) -> Result<Report> {
+        let (parsed, parse_time) = timed(|| S::parse(input));
+        let parsed = parsed.map_err(|e| e.in_day(S::DAY))?;
+        let (part_one, part_one_time) = timed(|| S::part_one(&parsed));
+        let (part_two, part_two_time) = timed(|| S::part_two(&parsed));
+        Ok(Report {
+            part_one,
+            part_two,
+            parse_time,
+            part_one_time,
+            part_two_time,
+        })
+    }
+
+    fn bench(&self, input: &str, iterations: usize) -> Result<Samples> {
+        let parsed = S::parse(input).map_err(|e| e.in_day(S::DAY))?;
+        let parse = (0..iterations)
+            .map(|_| timed(|| black_box(S::parse(black_box(input)))).1)
+            .collect();
+        let part_one = (0..iterations)
+            .map(|_| timed(|| black_box(S::part_one(black_box(&parsed)))).1)
+            .collect();
+        let part_two = (0..iterations)
+            .map(|_| timed(|| black_box(S::part_two(black_box(&parsed)))).1)
+            .collect();
+        Ok(Samples {
+            stages: [parse, part_one, part_two],
+        })
+    }
+}
+
+/// Every implemented day, in ascending order of day number.
+pub static SOLVERS: &[&dyn Runner] = &[
+    &day1::Day1,
+    &day2::Day2,
+    &day3::Day3,
+    &day4::Day4,
+    &day5::Day5,
+    &day6::Day6,
+    &day7::Day7,
+    &day8::Day8,
+    &day9::Day9,
+    &day10::Day10,
+    &day11::Day11,
+    &day12::Day12,
+    &day13::Day13,
+    &day14::Day14,
+    &day15::Day15,
+    &day16::Day16,
+    &day17::Day17,
+    &day18::Day18,
+    &day19::Day19,
+    &day20::Day20,
+    &day21::Day21,
+    &day22::Day22,
+    &day23::Day23,
+    &day24::Day24,
+    &day25::Day25,
+];
+
+pub fn find(day: u8) -> Option<&'static dyn Runner> {
+    SOLVERS.iter().copied().find(|s| s.day() == day)
+}
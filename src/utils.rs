@@ -1,19 +1,381 @@
-use std::fmt::Debug;
+use crate::error::{Error, Result};
+use std::fmt::{Display, Formatter};
 use std::io::Read;
+use std::path::PathBuf;
 use std::str::FromStr;
 
-pub(crate) fn input() -> String {
-    let mut buf = String::new();
-    std::io::stdin()
-        .lock()
-        .read_to_string(&mut buf)
-        .expect("Failed to read to buffer");
-    buf
+pub const DEFAULT_INPUT_DIR: &str = "inputs";
+
+/// Where a day's puzzle input is read from.
+#[derive(Clone, Debug)]
+pub enum InputSource {
+    /// A directory holding one `dayNN.txt` file per day.
+    Directory(PathBuf),
+    /// A single explicit file.
+    File(PathBuf),
+    Stdin,
+}
+
+impl Default for InputSource {
+    fn default() -> Self {
+        InputSource::Directory(PathBuf::from(DEFAULT_INPUT_DIR))
+    }
 }
 
-pub(crate) fn parse_input<T: FromStr>(s: &str) -> Vec<T>
+impl InputSource {
+    /// Interprets a command-line `--input` value, where `-` means stdin.
+    pub fn from_arg(arg: &str) -> Self {
+        if arg == "-" {
+            InputSource::Stdin
+        } else {
+            InputSource::File(PathBuf::from(arg))
+        }
+    }
+
+    /// The file the input for `day` would be read from, if it comes from a file at all.
+    pub fn path(&self, day: u8) -> Option<PathBuf> {
+        match self {
+            InputSource::Directory(dir) => Some(dir.join(format!("day{:02}.txt", day))),
+            InputSource::File(path) => Some(path.clone()),
+            InputSource::Stdin => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct InputError {
+    day: u8,
+    path: Option<PathBuf>,
+    source: std::io::Error,
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.path {
+            Some(path) if self.source.kind() == std::io::ErrorKind::NotFound => write!(
+                f,
+                "missing input for day {}: expected a file at {}",
+                self.day,
+                path.display()
+            ),
+            Some(path) => write!(
+                f,
+                "failed to read input for day {} from {}: {}",
+                self.day,
+                path.display(),
+                self.source
+            ),
+            None => write!(
+                f,
+                "failed to read input for day {} from stdin: {}",
+                self.day, self.source
+            ),
+        }
+    }
+}
+
+impl std::error::Error for InputError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+/// Reads the puzzle input for `day` from `source`.
+pub fn input(day: u8, source: &InputSource) -> std::result::Result<String, InputError> {
+    let path = source.path(day);
+    let read = match &path {
+        Some(path) => std::fs::read_to_string(path),
+        None => {
+            let mut buf = String::new();
+            std::io::stdin()
+                .lock()
+                .read_to_string(&mut buf)
+                .map(|_| buf)
+        }
+    };
+    read.map_err(|source| InputError { day, path, source })
+}
+
+/// Parses every line of `s` as a `T`.
+pub(crate) fn parse_input<T: FromStr>(s: &str) -> Result<Vec<T>>
 where
-    <T as FromStr>::Err: Debug,
+    <T as FromStr>::Err: Display,
 {
-    s.lines().flat_map(|sub| sub.parse()).collect()
+    s.lines()
+        .enumerate()
+        .map(|(i, line)| line.parse().map_err(|e| Error::line(i, line, e)))
+        .collect()
+}
+
+/// Parses a row of single-digit numbers, such as `2199943210`.
+pub(crate) fn parse_digits(line_index: usize, line: &str) -> Result<Vec<u8>> {
+    line.char_indices()
+        .map(|(i, ch)| {
+            ch.to_digit(10).map(|d| d as u8).ok_or_else(|| {
+                let token = &line[i..i + ch.len_utf8()];
+                Error::parse(line_index, line, token, "expected a digit")
+            })
+        })
+        .collect()
+}
+
+/// Parses each of `tokens`, which must be subslices of `line`, as a `T`.
+pub(crate) fn parse_tokens<'a, T: FromStr>(
+    line_index: usize,
+    line: &'a str,
+    tokens: impl Iterator<Item = &'a str>,
+) -> Result<Vec<T>>
+where
+    <T as FromStr>::Err: Display,
+{
+    tokens
+        .map(|token| {
+            token
+                .parse()
+                .map_err(|e| Error::parse(line_index, line, token, e))
+        })
+        .collect()
+}
+
+/// Runs `step` until it returns `true`, returning how many times it ran.
+pub(crate) fn steps_until(mut step: impl FnMut() -> bool) -> usize {
+    (1..).find(|_| step()).expect("Ran out of steps")
+}
+
+/// The `(row, column)` offsets of the 3x3 block centred on a cell, in row-major order.
+pub(crate) fn block_3x3() -> impl Iterator<Item = (isize, isize)> {
+    (-1isize..=1).flat_map(|dr| (-1isize..=1).map(move |dc| (dr, dc)))
+}
+
+/// A `(row, column)` position in a [`Grid`].
+pub type Position = (usize, usize);
+
+/// A rectangular grid of cells, stored row by row.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Builds a grid from its cells in row-major order.
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(width * height, cells.len(), "Grid must be fully populated");
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, (r, c): Position) -> Option<&T> {
+        (r < self.height && c < self.width).then(|| &self.cells[r * self.width + c])
+    }
+
+    /// Every cell, in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.cells.iter_mut()
+    }
+
+    /// Every position in the grid, in row-major order.
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let width = self.width;
+        (0..self.height).flat_map(move |r| (0..width).map(move |c| (r, c)))
+    }
+
+    pub fn row(&self, r: usize) -> &[T] {
+        &self.cells[r * self.width..(r + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` panics on a chunk size of zero, and a zero-width grid has no cells to chunk.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// The cells in column `c`, from top to bottom.
+    pub fn column(&self, c: usize) -> impl Iterator<Item = &T> {
+        assert!(c < self.width, "Column {} is outside the grid", c);
+        self.cells.iter().skip(c).step_by(self.width)
+    }
+
+    /// Builds a grid of the same shape by applying `f` to every cell.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid::new(self.width, self.height, self.cells.iter().map(f).collect())
+    }
+
+    /// The position `(dr, dc)` away from `(r, c)`, if it's inside the grid.
+    fn offset(&self, (r, c): Position, (dr, dc): (isize, isize)) -> Option<Position> {
+        let r = r.checked_add_signed(dr).filter(|&r| r < self.height)?;
+        let c = c.checked_add_signed(dc).filter(|&c| c < self.width)?;
+        Some((r, c))
+    }
+
+    /// The up to four positions orthogonally adjacent to `(r, c)`.
+    pub fn neighbors4(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        [(-1, 0), (1, 0), (0, -1), (0, 1)]
+            .into_iter()
+            .filter_map(move |offset| self.offset(position, offset))
+    }
+
+    /// The up to eight positions orthogonally or diagonally adjacent to `(r, c)`, in row-major
+    /// order.
+    pub fn neighbors8(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        block_3x3()
+            .filter(|&offset| offset != (0, 0))
+            .filter_map(move |offset| self.offset(position, offset))
+    }
+}
+
+impl<T> std::ops::Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (r, c): Position) -> &T {
+        assert!(
+            r < self.height && c < self.width,
+            "({}, {}) is outside the grid",
+            r,
+            c
+        );
+        &self.cells[r * self.width + c]
+    }
+}
+
+impl<T> std::ops::IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, (r, c): Position) -> &mut T {
+        assert!(
+            r < self.height && c < self.width,
+            "({}, {}) is outside the grid",
+            r,
+            c
+        );
+        &mut self.cells[r * self.width + c]
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    /// Draws each row on its own line, with no separators between cells.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (r, row) in self.rows().enumerate() {
+            if r > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Parses each line of `input` into a row of cells with `parse_row`, which is given each line's
+/// index and text. The rows must all be the same length.
+pub(crate) fn parse_grid<T>(
+    input: &str,
+    parse_row: impl FnMut(usize, &str) -> Result<Vec<T>>,
+) -> Result<Grid<T>> {
+    parse_grid_lines(input.lines().enumerate(), parse_row)
+}
+
+/// Like [`parse_grid`], but for a grid that is only part of the input, given as its lines along
+/// with their indices in the whole input.
+pub(crate) fn parse_grid_lines<'a, T>(
+    lines: impl IntoIterator<Item = (usize, &'a str)>,
+    mut parse_row: impl FnMut(usize, &str) -> Result<Vec<T>>,
+) -> Result<Grid<T>> {
+    let mut cells = Vec::new();
+    let mut width = None;
+    let mut height = 0;
+    for (i, line) in lines {
+        let row = parse_row(i, line)?;
+        if *width.get_or_insert(row.len()) != row.len() {
+            return Err(Error::line(i, line, "rows must all be the same length"));
+        }
+        cells.extend(row);
+        height += 1;
+    }
+    Ok(Grid::new(width.unwrap_or(0), height, cells))
+}
+
+/// Parses lines of single-digit numbers, all the same length, into a grid.
+pub(crate) fn parse_digit_grid(input: &str) -> Result<Grid<u8>> {
+    parse_grid(input, parse_digits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIGITS: &str = "123\n456\n";
+
+    #[test]
+    fn parses_digit_grids() {
+        let grid = parse_digit_grid(DIGITS).unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(1, 0)], 4);
+        assert_eq!(grid.get((0, 3)), None);
+        assert_eq!(grid.get((2, 0)), None);
+    }
+
+    #[test]
+    fn rejects_ragged_rows() {
+        assert!(parse_digit_grid("123\n45").is_err());
+        assert!(parse_digit_grid("123\n4x6").is_err());
+    }
+
+    #[test]
+    fn views_rows_and_columns() {
+        let grid = parse_digit_grid(DIGITS).unwrap();
+        assert_eq!(grid.row(1), &[4, 5, 6]);
+        assert_eq!(grid.rows().count(), 2);
+        assert_eq!(grid.column(2).copied().collect::<Vec<_>>(), vec![3, 6]);
+        let positions: Vec<_> = grid.positions().collect();
+        assert_eq!(positions.len(), 6);
+        assert_eq!(positions[3], (1, 0));
+    }
+
+    #[test]
+    fn finds_neighbors_within_bounds() {
+        let grid = parse_digit_grid("123\n456\n789").unwrap();
+        let corner: Vec<_> = grid.neighbors4((0, 0)).collect();
+        assert_eq!(corner, vec![(1, 0), (0, 1)]);
+        assert_eq!(grid.neighbors4((1, 1)).count(), 4);
+        let edge: Vec<_> = grid.neighbors8((0, 1)).collect();
+        assert_eq!(edge, vec![(0, 0), (0, 2), (1, 0), (1, 1), (1, 2)]);
+        assert_eq!(grid.neighbors8((1, 1)).count(), 8);
+    }
+
+    #[test]
+    fn indexes_mutably_and_maps() {
+        let mut grid = parse_digit_grid(DIGITS).unwrap();
+        grid[(0, 2)] = 9;
+        grid.iter_mut().for_each(|d| *d += 1);
+        let doubled = grid.map(|&d| 2 * d as u32);
+        assert_eq!(doubled.row(0), &[4, 6, 20]);
+    }
+
+    #[test]
+    fn displays_as_rows() {
+        let grid = parse_digit_grid(DIGITS).unwrap();
+        assert_eq!(grid.to_string(), "123\n456");
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_indexing_outside() {
+        let grid = parse_digit_grid(DIGITS).unwrap();
+        let _ = grid[(0, 3)];
+    }
 }